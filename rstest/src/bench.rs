@@ -0,0 +1,254 @@
+use std::{
+    fs::OpenOptions,
+    future::Future,
+    hint::black_box,
+    io::Write,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{outcome::TestOutcome, timeout::parse_duration};
+
+/// The environment variable that runs the `#[rstest(bench)]` tests as benchmarks: its value is
+/// the measurement time of each benchmark (i.e. `3s`, just a number are seconds) and `0`
+/// disables them. It's read when the test runs.
+pub const ENV: &str = "RSTEST_BENCH";
+
+/// The environment variable with the path of the file where to append the benchmarks results.
+pub const REPORT_ENV: &str = "RSTEST_BENCH_REPORT";
+
+/// How long a benchmark runs, if benchmarks are enabled.
+fn measurement_time() -> Option<Duration> {
+    std::env::var(ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            parse_duration(&value).unwrap_or_else(|e| panic!("Invalid {ENV} '{value}': {e}"))
+        })
+        .filter(|time| !time.is_zero())
+}
+
+/// The measured times of the benchmark function calls: every benchmark has its own.
+pub struct Samples(Mutex<Vec<Duration>>);
+
+impl Samples {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(Mutex::new(Vec::new()))
+    }
+
+    /// Measure the test function call, if benchmarks are enabled.
+    pub fn measure<T>(&self, code: impl FnOnce() -> T) -> T {
+        if measurement_time().is_none() {
+            return code();
+        }
+        let start = Instant::now();
+        let out = black_box(code());
+        self.push(start.elapsed());
+        out
+    }
+
+    /// Like `measure()` but for async test function calls.
+    pub async fn measure_async<T>(&self, code: impl Future<Output = T>) -> T {
+        if measurement_time().is_none() {
+            return code.await;
+        }
+        let start = Instant::now();
+        let out = black_box(code.await);
+        self.push(start.elapsed());
+        out
+    }
+
+    fn push(&self, sample: Duration) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(sample)
+    }
+
+    fn take(&self) -> Vec<Duration> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn len(&self) -> usize {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+}
+
+/// Run the test code once or, if benchmarks are enabled, again and again for the measurement
+/// time: the fixtures are resolved for every call but just the test function call is measured.
+pub fn run<T: TestOutcome>(samples: &Samples, test: &str, mut code: impl FnMut() -> T) -> T {
+    let Some(time) = measurement_time() else {
+        return code();
+    };
+    samples.take();
+    let start = Instant::now();
+    loop {
+        let out = code();
+        if out.failure().is_some() {
+            return out;
+        }
+        if done(samples, start, time) {
+            report(test, samples.take());
+            return out;
+        }
+    }
+}
+
+/// Like `run()` but for async test code.
+pub async fn run_async<T: TestOutcome, Fut: Future<Output = T>>(
+    samples: &Samples,
+    test: &str,
+    mut code: impl FnMut() -> Fut,
+) -> T {
+    let Some(time) = measurement_time() else {
+        return code().await;
+    };
+    samples.take();
+    let start = Instant::now();
+    loop {
+        let out = code().await;
+        if out.failure().is_some() {
+            return out;
+        }
+        if done(samples, start, time) {
+            report(test, samples.take());
+            return out;
+        }
+    }
+}
+
+/// The benchmark stops when the test is skipped (nothing measured) or when the measurement
+/// time is expired: the first call is a warm up, so we need two calls at least.
+fn done(samples: &Samples, start: Instant, time: Duration) -> bool {
+    let measured = samples.len();
+    measured == 0 || (measured > 1 && start.elapsed() >= time)
+}
+
+#[derive(Debug, PartialEq)]
+struct Stats {
+    iterations: usize,
+    mean: Duration,
+    std_dev: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+/// The statistics of the samples, but the warm up one.
+fn stats(samples: &[Duration]) -> Option<Stats> {
+    let samples = match samples {
+        [] => return None,
+        [sample] => std::slice::from_ref(sample),
+        [_warm_up, samples @ ..] => samples,
+    };
+    let iterations = samples.len();
+    let mean = samples.iter().sum::<Duration>() / iterations as u32;
+    let variance = samples
+        .iter()
+        .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+        .sum::<f64>()
+        / iterations as f64;
+    Some(Stats {
+        iterations,
+        mean,
+        std_dev: Duration::from_secs_f64(variance.sqrt()),
+        min: samples.iter().min().copied().unwrap_or_default(),
+        max: samples.iter().max().copied().unwrap_or_default(),
+    })
+}
+
+/// The results are written directly to the standard error to avoid the test harness capture,
+/// and appended to the `RSTEST_BENCH_REPORT` file if any.
+fn report(test: &str, samples: Vec<Duration>) {
+    let Some(stats) = stats(&samples) else {
+        return;
+    };
+    let _ = writeln!(
+        std::io::stderr().lock(),
+        "*** BENCH {test}: {:?}/iter (± {:?}, min {:?}, max {:?}, {} iterations) ***",
+        stats.mean,
+        stats.std_dev,
+        stats.min,
+        stats.max,
+        stats.iterations
+    );
+    if let Some(path) = std::env::var_os(REPORT_ENV) {
+        let appended = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{}", report_line(test, &stats)));
+        if let Err(e) = appended {
+            let _ = writeln!(
+                std::io::stderr().lock(),
+                "Cannot append benchmark results to {}: {e}",
+                path.to_string_lossy()
+            );
+        }
+    }
+}
+
+/// A tab separated line with test name, iterations and mean, standard deviation, min and max
+/// nanoseconds.
+fn report_line(test: &str, stats: &Stats) -> String {
+    format!(
+        "{test}\t{}\t{}\t{}\t{}\t{}",
+        stats.iterations,
+        stats.mean.as_nanos(),
+        stats.std_dev.as_nanos(),
+        stats.min.as_nanos(),
+        stats.max.as_nanos()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn should_compute_stats_without_warm_up() {
+        assert_eq!(
+            Some(Stats {
+                iterations: 2,
+                mean: ms(20),
+                std_dev: ms(10),
+                min: ms(10),
+                max: ms(30),
+            }),
+            stats(&[ms(100), ms(10), ms(30)])
+        );
+    }
+
+    #[test]
+    fn should_use_single_sample() {
+        assert_eq!(Some(ms(5)), stats(&[ms(5)]).map(|s| s.mean));
+        assert_eq!(None, stats(&[]));
+    }
+
+    #[test]
+    fn should_format_report_line() {
+        let stats = stats(&[ms(100), ms(10), ms(30)]).unwrap();
+
+        assert_eq!(
+            "a::case_1\t2\t20000000\t10000000\t10000000\t30000000",
+            report_line("a::case_1", &stats)
+        );
+    }
+
+    #[test]
+    fn should_run_just_once_if_not_enabled() {
+        static SAMPLES: Samples = Samples::new();
+        let mut calls = 0;
+
+        run(&SAMPLES, "test", || {
+            calls += 1;
+            SAMPLES.measure(|| 42);
+        });
+
+        assert_eq!(1, calls);
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    io::Read,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The environment variable that tells to the child process which test it should run.
+pub const ISOLATED_TEST_ENV: &str = "RSTEST_ISOLATED_TEST";
+
+/// The environment variable that isolates all the tests, if set and not `0`: it's read when
+/// the test runs and just by the tests compiled with the `isolate` feature.
+pub const ENV: &str = "RSTEST_ISOLATE";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The test name as reported by the test harness: `module_path` is the module of the
+/// test function (crate name included).
+pub fn test_name(module_path: &str, name: &str) -> String {
+    match module_path.split_once("::") {
+        Some((_crate, path)) => format!("{path}::{name}"),
+        None => name.to_owned(),
+    }
+}
+
+/// `true` if the tests should be isolated by the `RSTEST_ISOLATE` environment variable.
+pub fn by_env() -> bool {
+    std::env::var(ENV).map_or(false, |isolate| isolate != "0")
+}
+
+/// `true` if the current process is the child process that should run the given test.
+pub fn is_isolated(test: &str) -> bool {
+    std::env::var(ISOLATED_TEST_ENV).map_or(false, |name| name == test)
+}
+
+/// Run the given test in a child process of the current test binary and wait its outcome.
+/// If the child doesn't complete in `timeout` it will be killed.
+///
+/// Panics if the child test fails, is killed by a signal or times out. As for every test
+/// marked with `#[should_panic]` the outcome of a passed child is inverted: the test harness
+/// in the child process has already checked that the test panics, so this function panics
+/// and any failure is printed without panicking.
+pub fn run(test: &str, timeout: Option<Duration>, should_panic: bool) {
+    let child = Command::new(std::env::current_exe().expect("Cannot find the test executable"))
+        .args([
+            test,
+            "--exact",
+            "--nocapture",
+            "--include-ignored",
+            "--test-threads=1",
+        ])
+        .env(ISOLATED_TEST_ENV, test)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Cannot spawn the isolated test process");
+    let execution = Execution::wait(child, timeout);
+    let failure = match execution.status {
+        None => Some(format!(
+            "Timeout {:?} expired: isolated test process killed\n{}",
+            timeout.unwrap_or_default(),
+            execution.output
+        )),
+        Some(status) => match signal(&status) {
+            Some(signal) => Some(format!(
+                "Isolated test process killed by signal {signal}\n{}",
+                execution.output
+            )),
+            None if !execution.output.contains("running 1 test") => Some(format!(
+                "Cannot find test '{test}' in the isolated test process: {status}\n{}",
+                execution.output
+            )),
+            // The child harness has already inverted the outcome of a `should_panic` test
+            None if !status.success() && should_panic => Some(format!(
+                "Expected panic but isolated test process passed: {status}\n{}",
+                execution.output
+            )),
+            None if !status.success() => Some(format!(
+                "Isolated test process failed: {status}\n{}",
+                execution.output
+            )),
+            None => None,
+        },
+    };
+    // The parent process reports the skipped child test
+    if failure.is_none() {
+        if let Some(reason) = skip_reason(test, &execution.output) {
+            crate::report::skip(reason);
+        }
+    }
+    match (failure, should_panic) {
+        (Some(failure), false) => panic!("{failure}"),
+        // A panic would pass the test: the test harness reports that it didn't panic
+        (Some(failure), true) => print!("{failure}"),
+        (None, false) => print!("{}", execution.output),
+        (None, true) => panic!(
+            "Isolated test process panicked as expected\n{}",
+            execution.output
+        ),
+    }
+}
+
+struct Execution {
+    /// `None` if the process was killed by timeout
+    status: Option<ExitStatus>,
+    output: String,
+}
+
+impl Execution {
+    fn wait(mut child: Child, timeout: Option<Duration>) -> Self {
+        let stdout = child.stdout.take().map(read_all);
+        let stderr = child.stderr.take().map(read_all);
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let status = loop {
+            match child
+                .try_wait()
+                .expect("Cannot wait the isolated test process")
+            {
+                Some(status) => break Some(status),
+                None if deadline.map_or(false, |deadline| Instant::now() >= deadline) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break None;
+                }
+                None => thread::sleep(POLL_INTERVAL),
+            }
+        };
+        let output = [stdout, stderr]
+            .into_iter()
+            .flatten()
+            .map(|reader| reader.join().unwrap_or_default())
+            .collect();
+        Self { status, output }
+    }
+}
+
+/// The reason of the `#[skip_if]` that skipped the test in the child process, if any.
+fn skip_reason<'a>(test: &str, output: &'a str) -> Option<&'a str> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix(&format!("SKIPPED {test}: ")))
+}
+
+fn read_all(mut source: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = source.read_to_end(&mut buffer);
+        String::from_utf8_lossy(&buffer).into_owned()
+    })
+}
+
+#[cfg(unix)]
+fn signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_remove_crate_name_from_test_name() {
+        assert_eq!("a::b::test", test_name("my_crate::a::b", "test"));
+        assert_eq!("test", test_name("my_crate", "test"));
+    }
+
+    #[test]
+    fn should_not_isolate_by_env_if_not_set() {
+        assert!(!by_env());
+    }
+
+    #[test]
+    fn should_not_be_isolated_if_not_in_child_process() {
+        assert!(!is_isolated(
+            "tests::should_not_be_isolated_if_not_in_child_process"
+        ));
+    }
+
+    #[test]
+    fn should_find_the_skip_reason_of_the_child_test() {
+        let output = "running 1 test\nSKIPPED a::other: no\nSKIPPED a::test: no db\n";
+
+        assert_eq!(Some("no db"), skip_reason("a::test", output));
+        assert_eq!(None, skip_reason("a::missed", output));
+    }
+}
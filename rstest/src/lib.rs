@@ -234,16 +234,60 @@
 //! speed up compilation.
 //!
 //! - **`async-timeout`** *(enabled by default)* — Implement timeout for async
-//! tests.
+//!   tests.
+//! - **`tokio`** — Use `#[tokio::test]` as the default attribute for async tests.
+//! - **`tokio-multi-thread`** — Like `tokio` but use the multi thread runtime flavor.
+//! - **`smol`** — Run async tests in `smol::block_on()`.
+//! - **`async-std`** — Use `#[async_std::test]` for async tests: this is the default if no
+//!   other runtime is selected.
+//! - **`tracing`** — Emit the `#[trace]`d arguments as `tracing` events instead of printing
+//!   them.
+//! - **`log`** — Emit the `#[trace]`d arguments as `log` records instead of printing them
+//!   (`tracing` wins if both are enabled).
+//! - **`registry`** — Register the metadata of the generated tests in
+//!   [`registry`](crate::registry): list them at runtime with `rstest::registry::iter()`.
+//! - **`isolate`** — Run every test in its own child process when the `RSTEST_ISOLATE`
+//!   environment variable is set, like `#[isolate]` does for a single test.
+//! - **`warn-slow`** — Warn about the passed tests slower than the `RSTEST_WARN_SLOW`
+//!   threshold, also when they don't have a `#[warn_slow]` attribute.
+//! - **`report`** — Write the JSON Lines and JUnit XML test reports requested by the
+//!   `RSTEST_REPORT_JSON` and `RSTEST_REPORT_JUNIT` environment variables.
+//! - **`tags`** — Skip also the untagged tests when `RSTEST_TAGS` selects some tags.
 //!
 //! # Rust version compatibility
 //!
 //! The minimum supported Rust version is 1.67.1.
 //!
 
+#[doc(hidden)]
+pub mod bench;
+#[doc(hidden)]
+pub mod isolate;
 #[doc(hidden)]
 pub mod magic_conversion;
 #[doc(hidden)]
+pub mod outcome;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[doc(hidden)]
+pub mod repeat;
+#[doc(hidden)]
+pub mod report;
+#[doc(hidden)]
+pub mod retry;
+#[doc(hidden)]
+pub mod serial;
+#[doc(hidden)]
+pub mod skip;
+#[doc(hidden)]
+pub mod slow;
+#[doc(hidden)]
+pub mod tags;
+#[doc(hidden)]
 pub mod timeout;
+#[doc(hidden)]
+pub mod trace;
+#[doc(hidden)]
+pub mod xfail;
 
 pub use rstest_macros::{fixture, rstest};
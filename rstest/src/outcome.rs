@@ -0,0 +1,231 @@
+use std::{
+    any::Any,
+    fmt::Debug,
+    future::Future,
+    marker::PhantomData,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The outcome of a test function: `()` always passes, a `Result` fails if it's an `Err`.
+pub trait TestOutcome {
+    /// The failure message if the test failed.
+    fn failure(&self) -> Option<String>;
+}
+
+impl TestOutcome for () {
+    fn failure(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T, E: Debug> TestOutcome for Result<T, E> {
+    fn failure(&self) -> Option<String> {
+        self.as_ref().err().map(|e| format!("Error: {e:?}"))
+    }
+}
+
+/// The failure of any test output: the generated code calls `(&&&Failure(&output)).failure()`
+/// that is always `None` for the types that don't implement `TestOutcome`.
+pub struct Failure<'a, T>(pub &'a T);
+
+pub trait ViaTestOutcome {
+    fn failure(&self) -> Option<String>;
+}
+
+impl<T: TestOutcome> ViaTestOutcome for &&Failure<'_, T> {
+    fn failure(&self) -> Option<String> {
+        self.0.failure()
+    }
+}
+
+pub trait Unknown {
+    fn failure(&self) -> Option<String>;
+}
+
+impl<T> Unknown for &Failure<'_, T> {
+    fn failure(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The value returned by a test that passed without running its code (i.e. skipped by
+/// `#[skip_if]` or run in an isolated process).
+pub trait Passed {
+    fn passed() -> Self;
+}
+
+impl Passed for () {
+    fn passed() -> Self {}
+}
+
+impl<T: Passed, E> Passed for Result<T, E> {
+    fn passed() -> Self {
+        Ok(T::passed())
+    }
+}
+
+impl Passed for std::process::ExitCode {
+    fn passed() -> Self {
+        Self::SUCCESS
+    }
+}
+
+/// The passing value of any test output type: the generated code calls
+/// `(&&&PassedValue::<T>(PhantomData)).passed()` that panics for the types that don't
+/// implement `Passed`.
+pub struct PassedValue<T>(pub PhantomData<T>);
+
+pub trait ViaPassed {
+    type Output;
+    fn passed(&self) -> Self::Output;
+}
+
+impl<T: Passed> ViaPassed for &&PassedValue<T> {
+    type Output = T;
+    fn passed(&self) -> Self::Output {
+        T::passed()
+    }
+}
+
+pub trait NotPassed {
+    type Output;
+    fn passed(&self) -> Self::Output;
+}
+
+impl<T> NotPassed for &PassedValue<T> {
+    type Output = T;
+    fn passed(&self) -> Self::Output {
+        panic!(
+            "Cannot pass the test without running it: `{}` doesn't implement `rstest::outcome::Passed`",
+            std::any::type_name::<T>()
+        )
+    }
+}
+
+/// Run the test code and return `Err(message)` if it panics or returns a failure.
+pub fn catch_sync<T: TestOutcome, F: FnOnce() -> T>(code: F) -> Result<(), String> {
+    outcome(catch_unwind(AssertUnwindSafe(code)))
+}
+
+/// Like `catch_sync()` but for async test code.
+pub async fn catch_async<T: TestOutcome, Fut: Future<Output = T>>(code: Fut) -> Result<(), String> {
+    outcome(CatchUnwind(Box::pin(code)).await)
+}
+
+fn outcome<T: TestOutcome>(result: Result<T, Box<dyn Any + Send>>) -> Result<(), String> {
+    match result {
+        Ok(out) => out.failure().map(Err).unwrap_or(Ok(())),
+        Err(payload) => Err(panic_message(payload.as_ref())),
+    }
+}
+
+/// Extract the message from a panic payload.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_owned())
+}
+
+pub(crate) struct CatchUnwind<Fut>(pub(crate) Pin<Box<Fut>>);
+
+impl<Fut: Future> Future for CatchUnwind<Fut> {
+    type Output = Result<Fut::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = self.0.as_mut();
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(out)) => Poll::Ready(Ok(out)),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pass_if_no_panic_and_no_error() {
+        assert_eq!(Ok(()), catch_sync(|| ()));
+        assert_eq!(Ok(()), catch_sync(|| Ok::<_, String>(42)));
+    }
+
+    #[test]
+    fn should_get_the_failure_of_any_output() {
+        assert_eq!(None, (&&&Failure(&())).failure());
+        assert_eq!(
+            Some(r#"Error: "failed""#.to_owned()),
+            (&&&Failure(&Err::<(), _>("failed"))).failure()
+        );
+        assert_eq!(
+            None,
+            (&&&Failure(&std::process::ExitCode::FAILURE)).failure()
+        );
+    }
+
+    #[test]
+    fn should_return_a_passing_value() {
+        <() as Passed>::passed();
+        assert_eq!(Ok(()), Result::<(), String>::passed());
+        assert_eq!(
+            std::process::ExitCode::SUCCESS,
+            std::process::ExitCode::passed()
+        );
+    }
+
+    #[test]
+    fn should_return_a_passing_value_of_any_type() {
+        assert_eq!(
+            Ok(()),
+            (&&&PassedValue::<Result<(), String>>(PhantomData)).passed()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`u32` doesn't implement `rstest::outcome::Passed`")]
+    fn should_panic_if_the_type_has_no_passing_value() {
+        (&&&PassedValue::<u32>(PhantomData)).passed();
+    }
+
+    #[test]
+    fn should_return_error_message() {
+        assert_eq!(
+            Err(r#"Error: "failed""#.to_owned()),
+            catch_sync(|| Err::<(), _>("failed"))
+        );
+    }
+
+    fn boom(n: u32) {
+        panic!("boom {}", n)
+    }
+
+    fn static_boom() {
+        std::panic::panic_any("static boom")
+    }
+
+    async fn async_boom() {
+        async_std::task::yield_now().await;
+        panic!("boom")
+    }
+
+    #[test]
+    fn should_return_panic_message() {
+        assert_eq!(Err("boom 42".to_owned()), catch_sync(|| boom(42)));
+        assert_eq!(Err("static boom".to_owned()), catch_sync(static_boom));
+    }
+
+    #[async_std::test]
+    async fn should_catch_async_panics_and_errors() {
+        assert_eq!(Ok(()), catch_async(async {}).await);
+        assert_eq!(
+            Err("Error: 42".to_owned()),
+            catch_async(async { Err::<(), _>(42) }).await
+        );
+        assert_eq!(Err("boom".to_owned()), catch_async(async_boom()).await);
+    }
+}
@@ -0,0 +1,255 @@
+//! The tests generated by `#[rstest]`, collected at link time: enable the `registry` feature
+//! to use it.
+//!
+//! Every test generated by `#[rstest]` registers its metadata: the source function, the
+//! generated test path, the case description, the values of the parametrized arguments as
+//! written in the source code, the tags, the main attributes and the source location. You
+//! can use them to build your own tooling, like coverage matrices or docs tables.
+//!
+//! ```
+//! # #[cfg(feature = "registry")]
+//! # {
+//! for test in rstest::registry::iter() {
+//!     println!(
+//!         "{} ({}:{}) {:?}",
+//!         test.name(),
+//!         test.file(),
+//!         test.line(),
+//!         test.parameters()
+//!     );
+//! }
+//! # }
+//! ```
+//!
+//! Only the tests compiled in the current test binary are registered.
+
+#[doc(hidden)]
+pub use inventory;
+
+/// The metadata of a test generated by `#[rstest]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Test {
+    path: &'static str,
+    function: &'static str,
+    case: Option<&'static str>,
+    parameters: &'static [(&'static str, &'static str)],
+    tags: &'static [&'static str],
+    timeout: Option<&'static str>,
+    should_panic: bool,
+    ignore: bool,
+    attributes: &'static [&'static str],
+    file: &'static str,
+    line: u32,
+    column: u32,
+}
+
+inventory::collect!(Test);
+
+/// Iterate over all the tests of the current test binary, in no particular order.
+pub fn iter() -> impl Iterator<Item = &'static Test> {
+    inventory::iter::<Test>.into_iter()
+}
+
+impl Test {
+    #[doc(hidden)]
+    pub const fn new(
+        path: &'static str,
+        function: &'static str,
+        file: &'static str,
+        line: u32,
+        column: u32,
+    ) -> Self {
+        Self {
+            path,
+            function,
+            case: None,
+            parameters: &[],
+            tags: &[],
+            timeout: None,
+            should_panic: false,
+            ignore: false,
+            attributes: &[],
+            file,
+            line,
+            column,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_case(self, case: &'static str) -> Self {
+        Self {
+            case: Some(case),
+            ..self
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_parameters(
+        self,
+        parameters: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        Self { parameters, ..self }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_tags(self, tags: &'static [&'static str]) -> Self {
+        Self { tags, ..self }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_timeout(self, timeout: &'static str) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_should_panic(self) -> Self {
+        Self {
+            should_panic: true,
+            ..self
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_ignore(self) -> Self {
+        Self {
+            ignore: true,
+            ..self
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_attributes(self, attributes: &'static [&'static str]) -> Self {
+        Self { attributes, ..self }
+    }
+
+    /// The full path of the generated test function, crate name included (i.e.
+    /// `my_crate::tests::sum::case_1`).
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The test name as reported by the test harness: the test path without the crate name.
+    pub fn name(&self) -> &'static str {
+        self.path
+            .split_once("::")
+            .map(|(_crate, name)| name)
+            .unwrap_or(self.path)
+    }
+
+    /// The name of the function marked by `#[rstest]`.
+    pub fn function(&self) -> &'static str {
+        self.function
+    }
+
+    /// The module of the function marked by `#[rstest]`, crate name included.
+    pub fn module(&self) -> &'static str {
+        let function = format!("::{}", self.function);
+        // Generated tests live in a module named as the function (if any)
+        self.path
+            .rfind(&format!("{function}::"))
+            .or_else(|| {
+                self.path
+                    .ends_with(&function)
+                    .then(|| self.path.len() - function.len())
+            })
+            .map(|end| &self.path[..end])
+            .unwrap_or_default()
+    }
+
+    /// The case description, if any (i.e. `big` for `#[case::big(...)]`).
+    pub fn case(&self) -> Option<&'static str> {
+        self.case
+    }
+
+    /// The `#[case]` arguments, the `#[values]` chosen (or their name) and the `#[files]`
+    /// paths used by this test: argument names and values as written in the source code.
+    pub fn parameters(&self) -> &'static [(&'static str, &'static str)] {
+        self.parameters
+    }
+
+    /// The `#[tag]`s of the test: the case and values ones combined with the function ones.
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    /// The `#[timeout]` duration as written in the source code, if any.
+    pub fn timeout(&self) -> Option<&'static str> {
+        self.timeout
+    }
+
+    /// `true` if the test is marked by `#[should_panic]`.
+    pub fn should_panic(&self) -> bool {
+        self.should_panic
+    }
+
+    /// `true` if the test is marked by `#[ignore]`.
+    pub fn ignore(&self) -> bool {
+        self.ignore
+    }
+
+    /// The attributes of the generated test, except the `rstest` ones, as written in the
+    /// source code.
+    pub fn attributes(&self) -> &'static [&'static str] {
+        self.attributes
+    }
+
+    /// The source file of the test function.
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line of the `#[rstest]` attribute.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The column of the `#[rstest]` attribute.
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST: Test = Test::new(
+        "my_crate::tests::sum::case_1_one",
+        "sum",
+        "src/lib.rs",
+        10,
+        1,
+    )
+    .with_case("one")
+    .with_parameters(&[("a", "1")])
+    .with_tags(&["db"])
+    .with_timeout("Duration::from_secs(1)")
+    .with_should_panic();
+
+    #[test]
+    fn should_build_test_metadata() {
+        assert_eq!("tests::sum::case_1_one", TEST.name());
+        assert_eq!(Some("one"), TEST.case());
+        assert_eq!(&[("a", "1")], TEST.parameters());
+        assert_eq!(&["db"], TEST.tags());
+        assert_eq!(Some("Duration::from_secs(1)"), TEST.timeout());
+        assert!(TEST.should_panic());
+        assert!(!TEST.ignore());
+    }
+
+    #[test]
+    fn should_find_function_module() {
+        assert_eq!("my_crate::tests", TEST.module());
+        assert_eq!(
+            "my_crate::sum",
+            Test::new("my_crate::sum::sum::case_1", "sum", "", 0, 0).module()
+        );
+        assert_eq!(
+            "my_crate::tests",
+            Test::new("my_crate::tests::single", "single", "", 0, 0).module()
+        );
+    }
+}
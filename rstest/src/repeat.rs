@@ -0,0 +1,190 @@
+use std::{
+    future::Future,
+    io::Write,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Mutex, PoisonError,
+    },
+    thread,
+};
+
+use crate::outcome::{catch_sync, panic_message, CatchUnwind, Passed, TestOutcome};
+
+/// Run the test code `iterations` times: stop at the first failing iteration.
+pub fn run<T: TestOutcome>(test: &str, iterations: u32, mut code: impl FnMut() -> T) -> T {
+    let mut iteration = 1;
+    loop {
+        let result = catch_unwind(AssertUnwindSafe(&mut code));
+        match check(test, iteration, iterations, result) {
+            Some(out) => return out,
+            None => iteration += 1,
+        }
+    }
+}
+
+/// Like `run()` but for async test code.
+pub async fn run_async<T: TestOutcome, Fut: Future<Output = T>>(
+    test: &str,
+    iterations: u32,
+    mut code: impl FnMut() -> Fut,
+) -> T {
+    let mut iteration = 1;
+    loop {
+        let result = CatchUnwind(Box::pin(code())).await;
+        match check(test, iteration, iterations, result) {
+            Some(out) => return out,
+            None => iteration += 1,
+        }
+    }
+}
+
+/// Run the test code `iterations` times concurrently on `threads` threads: the test fails with
+/// the failure of the first failing iteration and the threads don't start new iterations after
+/// a failure.
+pub fn run_concurrent<T: TestOutcome + Passed>(
+    test: &str,
+    iterations: u32,
+    threads: u32,
+    code: impl Fn() -> T + Sync,
+) -> T {
+    let next = AtomicU32::new(1);
+    let failure = Mutex::new(None::<(u32, String)>);
+    let failed = || {
+        failure
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    };
+    thread::scope(|scope| {
+        for _ in 0..threads.min(iterations) {
+            let builder = match thread::current().name() {
+                Some(name) => thread::Builder::new().name(name.to_string()),
+                None => thread::Builder::new(),
+            };
+            builder
+                .spawn_scoped(scope, || {
+                    while !failed() {
+                        let iteration = next.fetch_add(1, Ordering::SeqCst);
+                        if iteration > iterations {
+                            break;
+                        }
+                        if let Err(message) = catch_sync(&code) {
+                            let mut failure =
+                                failure.lock().unwrap_or_else(PoisonError::into_inner);
+                            if failure
+                                .as_ref()
+                                .map_or(true, |(first, _)| iteration < *first)
+                            {
+                                *failure = Some((iteration, message));
+                            }
+                        }
+                    }
+                })
+                .unwrap();
+        }
+    });
+    match failure.into_inner().unwrap_or_else(PoisonError::into_inner) {
+        Some((iteration, message)) => {
+            warn(test, iteration, iterations, &message);
+            panic!("{message}")
+        }
+        None => T::passed(),
+    }
+}
+
+/// Return the iteration outcome if it failed or if it's the last one, `None` if the test
+/// should go on.
+fn check<T: TestOutcome>(
+    test: &str,
+    iteration: u32,
+    iterations: u32,
+    result: thread::Result<T>,
+) -> Option<T> {
+    let failure = match &result {
+        Ok(out) => out.failure(),
+        Err(payload) => Some(panic_message(payload.as_ref())),
+    };
+    match failure {
+        Some(failure) => warn(test, iteration, iterations, &failure),
+        None if iteration < iterations => return None,
+        None => {}
+    }
+    Some(result.unwrap_or_else(|payload| resume_unwind(payload)))
+}
+
+/// The message is written directly to the standard error to avoid the test harness capture.
+fn warn(test: &str, iteration: u32, iterations: u32, failure: &str) {
+    let _ = writeln!(
+        std::io::stderr().lock(),
+        "*** REPEAT {test}: iteration {iteration} of {iterations} failed: {failure} ***"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_run_all_iterations() {
+        let mut iterations = 0;
+
+        run("test", 10, || iterations += 1);
+
+        assert_eq!(10, iterations);
+    }
+
+    #[test]
+    fn should_stop_at_first_failure() {
+        let mut iterations = 0;
+
+        let out = run("test", 10, || {
+            iterations += 1;
+            if iterations == 3 {
+                Err(iterations)
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(Err(3), out);
+        assert_eq!(3, iterations);
+    }
+
+    #[test]
+    fn should_run_all_iterations_concurrently() {
+        let iterations = AtomicU32::new(0);
+
+        run_concurrent("test", 100, 4, || {
+            iterations.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(100, iterations.into_inner());
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: \"boom\"")]
+    fn should_fail_if_any_concurrent_iteration_fails() {
+        let iterations = AtomicU32::new(0);
+
+        let _ = run_concurrent("test", 100, 4, || {
+            match iterations.fetch_add(1, Ordering::SeqCst) {
+                42 => Err("boom"),
+                _ => Ok(()),
+            }
+        });
+    }
+
+    #[async_std::test]
+    async fn should_run_all_async_iterations() {
+        let mut iterations = 0;
+
+        run_async("test", 5, || {
+            iterations += 1;
+            async_std::task::yield_now()
+        })
+        .await;
+
+        assert_eq!(5, iterations);
+    }
+}
@@ -0,0 +1,407 @@
+use std::{
+    cell::RefCell,
+    fmt::Write as _,
+    fs::OpenOptions,
+    future::Future,
+    io::Write,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    path::PathBuf,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::outcome::{panic_message, CatchUnwind};
+
+/// The environment variable with the path of the JSON Lines file where to append a record for
+/// each executed test.
+pub const JSON_ENV: &str = "RSTEST_REPORT_JSON";
+
+/// The environment variable with the directory where to write a JUnit XML report for each
+/// test binary.
+pub const JUNIT_ENV: &str = "RSTEST_REPORT_JUNIT";
+
+thread_local! {
+    /// The reason of the `#[skip_if]` that skipped the test running on this thread.
+    static SKIPPED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// The tests reported by this process: the JUnit report is rewritten from them after each
+/// test.
+static RECORDS: Mutex<Vec<Record>> = Mutex::new(Vec::new());
+
+/// What a test knows about itself: the test function, the case description, the
+/// `#[case]`, `#[values]` and `#[files]` arguments as written in the source code and the tags.
+pub struct Test {
+    name: String,
+    function: &'static str,
+    case: Option<&'static str>,
+    parameters: &'static [(&'static str, &'static str)],
+    tags: &'static [&'static str],
+    should_panic: bool,
+}
+
+impl Test {
+    /// `module_path` is the module of the test function (crate name included).
+    pub fn new(
+        module_path: &str,
+        name: &str,
+        function: &'static str,
+        case: Option<&'static str>,
+        parameters: &'static [(&'static str, &'static str)],
+        tags: &'static [&'static str],
+        should_panic: bool,
+    ) -> Self {
+        Self {
+            name: format!("{module_path}::{name}"),
+            function,
+            case,
+            parameters,
+            tags,
+            should_panic,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Status {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+#[derive(Debug, Clone)]
+struct Record {
+    suite: String,
+    name: String,
+    function: &'static str,
+    case: Option<&'static str>,
+    parameters: &'static [(&'static str, &'static str)],
+    tags: &'static [&'static str],
+    status: Status,
+    duration: Duration,
+}
+
+/// The isolated child processes don't report: the parent process records the outcome of the
+/// isolated test, otherwise the child would rewrite the JUnit report with just its test.
+fn enabled() -> bool {
+    (std::env::var_os(JSON_ENV).is_some() || std::env::var_os(JUNIT_ENV).is_some())
+        && std::env::var_os(crate::isolate::ISOLATED_TEST_ENV).is_none()
+}
+
+/// Run the test code and report its outcome, if a report is requested: `failure` gives the
+/// failure message of the returned value, if any.
+pub fn run<T>(
+    test: Test,
+    code: impl FnOnce() -> T,
+    failure: impl FnOnce(&T) -> Option<String>,
+) -> T {
+    if !enabled() {
+        return code();
+    }
+    let start = Instant::now();
+    skipped();
+    let result = catch_unwind(AssertUnwindSafe(code));
+    report(test, start.elapsed(), &result, failure);
+    result.unwrap_or_else(|payload| resume_unwind(payload))
+}
+
+/// Like `run()` but for async test code.
+pub async fn run_async<T>(
+    test: Test,
+    code: impl Future<Output = T>,
+    failure: impl FnOnce(&T) -> Option<String>,
+) -> T {
+    if !enabled() {
+        return code.await;
+    }
+    let start = Instant::now();
+    skipped();
+    let result = CatchUnwind(Box::pin(code)).await;
+    report(test, start.elapsed(), &result, failure);
+    result.unwrap_or_else(|payload| resume_unwind(payload))
+}
+
+/// Mark the test running on this thread as skipped.
+pub(crate) fn skip(reason: &str) {
+    SKIPPED.with(|skipped| *skipped.borrow_mut() = Some(reason.to_owned()))
+}
+
+/// Take the skip reason of the test running on this thread, if any.
+fn skipped() -> Option<String> {
+    SKIPPED.with(|skipped| skipped.borrow_mut().take())
+}
+
+fn report<T>(
+    test: Test,
+    duration: Duration,
+    result: &Result<T, Box<dyn std::any::Any + Send>>,
+    failure: impl FnOnce(&T) -> Option<String>,
+) {
+    let failure = match result {
+        Ok(out) => failure(out),
+        Err(payload) => Some(panic_message(payload.as_ref())),
+    };
+    let status = match (failure, test.should_panic) {
+        (Some(failure), false) => Status::Failed(failure),
+        (None, true) => Status::Failed("test did not panic as expected".to_owned()),
+        _ => match skipped() {
+            Some(reason) => Status::Skipped(reason),
+            None => Status::Passed,
+        },
+    };
+    let (suite, name) = test
+        .name
+        .split_once("::")
+        .map(|(suite, name)| (suite.to_owned(), name.to_owned()))
+        .unwrap_or_default();
+    let record = Record {
+        suite,
+        name,
+        function: test.function,
+        case: test.case,
+        parameters: test.parameters,
+        tags: test.tags,
+        status,
+        duration,
+    };
+    let mut records = RECORDS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(path) = std::env::var_os(JSON_ENV) {
+        let appended = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{}", json_line(&record)));
+        if let Err(e) = appended {
+            warn(&format!(
+                "Cannot append test report to {}: {e}",
+                path.to_string_lossy()
+            ));
+        }
+    }
+    records.push(record);
+    if let Some(dir) = std::env::var_os(JUNIT_ENV) {
+        let path = PathBuf::from(dir).join(format!("{}.xml", binary_name()));
+        if let Err(e) = std::fs::write(&path, junit(&records)) {
+            warn(&format!(
+                "Cannot write JUnit report {}: {e}",
+                path.to_string_lossy()
+            ));
+        }
+    }
+}
+
+fn warn(message: &str) {
+    let _ = writeln!(std::io::stderr().lock(), "{message}");
+}
+
+/// The name of the test binary: JUnit reports of different binaries don't overwrite each
+/// other.
+fn binary_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            exe.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "rstest".to_owned())
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_line(record: &Record) -> String {
+    let (status, message) = match &record.status {
+        Status::Passed => ("passed", None),
+        Status::Failed(message) => ("failed", Some(message)),
+        Status::Skipped(reason) => ("skipped", Some(reason)),
+    };
+    let parameters = record
+        .parameters
+        .iter()
+        .map(|(name, value)| format!("{}:{}", json_string(name), json_string(value)))
+        .collect::<Vec<_>>()
+        .join(",");
+    let tags = record
+        .tags
+        .iter()
+        .map(|tag| json_string(tag))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"suite":{},"test":{},"function":{},"case":{},"parameters":{{{parameters}}},"tags":[{tags}],"status":"{status}","duration":{},"message":{}}}"#,
+        json_string(&record.suite),
+        json_string(&record.name),
+        json_string(record.function),
+        record
+            .case
+            .map(json_string)
+            .unwrap_or_else(|| "null".to_owned()),
+        record.duration.as_secs_f64(),
+        message
+            .map(|m| json_string(m))
+            .unwrap_or_else(|| "null".to_owned()),
+    )
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_owned(),
+            '<' => "&lt;".to_owned(),
+            '>' => "&gt;".to_owned(),
+            '"' => "&quot;".to_owned(),
+            '\'' => "&apos;".to_owned(),
+            '\n' => "&#10;".to_owned(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn junit(records: &[Record]) -> String {
+    let count = |f: fn(&Status) -> bool| records.iter().filter(|r| f(&r.status)).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+        xml_escape(&records.first().map(|r| r.suite.clone()).unwrap_or_default()),
+        records.len(),
+        count(|s| matches!(s, Status::Failed(_))),
+        count(|s| matches!(s, Status::Skipped(_))),
+        records
+            .iter()
+            .map(|r| r.duration)
+            .sum::<Duration>()
+            .as_secs_f64()
+    );
+    for record in records {
+        let (module, name) = record
+            .name
+            .rsplit_once("::")
+            .unwrap_or(("", record.name.as_str()));
+        let classname = [record.suite.as_str(), module]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("::");
+        let _ = writeln!(
+            xml,
+            r#"  <testcase name="{}" classname="{}" time="{:.3}">"#,
+            xml_escape(name),
+            xml_escape(&classname),
+            record.duration.as_secs_f64()
+        );
+        xml.push_str("    <properties>\n");
+        let properties = [("function", record.function)]
+            .into_iter()
+            .chain(record.case.map(|case| ("case", case)))
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .chain((!record.tags.is_empty()).then(|| ("tags".to_owned(), record.tags.join(","))))
+            .chain(
+                record
+                    .parameters
+                    .iter()
+                    .map(|(name, value)| (format!("parameter.{name}"), value.to_string())),
+            );
+        for (name, value) in properties {
+            let _ = writeln!(
+                xml,
+                r#"      <property name="{}" value="{}"/>"#,
+                xml_escape(&name),
+                xml_escape(&value)
+            );
+        }
+        xml.push_str("    </properties>\n");
+        match &record.status {
+            Status::Passed => {}
+            Status::Failed(message) => {
+                let _ = writeln!(xml, r#"    <failure message="{}"/>"#, xml_escape(message));
+            }
+            Status::Skipped(reason) => {
+                let _ = writeln!(xml, r#"    <skipped message="{}"/>"#, xml_escape(reason));
+            }
+        }
+        xml.push_str("  </testcase>\n");
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(status: Status) -> Record {
+        Record {
+            suite: "my_crate".to_owned(),
+            name: "tests::sum::case_1_small".to_owned(),
+            function: "sum",
+            case: Some("small"),
+            parameters: &[("a", "1"), ("s", r#""a <b>""#)],
+            tags: &["db", "slow"],
+            status,
+            duration: Duration::from_millis(1500),
+        }
+    }
+
+    #[test]
+    fn should_escape_json_strings() {
+        assert_eq!(r#""a\"b\\c\nd\u0001""#, json_string("a\"b\\c\nd\u{1}"));
+    }
+
+    #[test]
+    fn should_format_json_line() {
+        assert_eq!(
+            r#"{"suite":"my_crate","test":"tests::sum::case_1_small","function":"sum","case":"small","parameters":{"a":"1","s":"\"a <b>\""},"tags":["db","slow"],"status":"failed","duration":1.5,"message":"boom"}"#,
+            json_line(&record(Status::Failed("boom".to_owned())))
+        );
+    }
+
+    #[test]
+    fn should_format_junit_report() {
+        let xml = junit(&[
+            record(Status::Passed),
+            record(Status::Failed("a < b".to_owned())),
+            record(Status::Skipped("no db".to_owned())),
+        ]);
+
+        assert!(xml.contains(
+            r#"<testsuite name="my_crate" tests="3" failures="1" skipped="1" time="4.500">"#
+        ));
+        assert!(xml.contains(
+            r#"<testcase name="case_1_small" classname="my_crate::tests::sum" time="1.500">"#
+        ));
+        assert!(xml.contains(r#"<property name="case" value="small"/>"#));
+        assert!(xml.contains(r#"<property name="tags" value="db,slow"/>"#));
+        assert!(xml.contains(r#"<property name="parameter.s" value="&quot;a &lt;b&gt;&quot;"/>"#));
+        assert!(xml.contains(r#"<failure message="a &lt; b"/>"#));
+        assert!(xml.contains(r#"<skipped message="no db"/>"#));
+    }
+
+    #[test]
+    fn should_run_code_if_not_enabled() {
+        let test = Test::new("my_crate::tests", "a_test", "a_test", None, &[], &[], false);
+
+        assert_eq!(
+            Ok(42),
+            run(test, || Ok::<_, String>(42), |_| unreachable!())
+        );
+    }
+}
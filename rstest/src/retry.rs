@@ -0,0 +1,168 @@
+use std::{
+    fs::OpenOptions,
+    future::Future,
+    io::Write,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
+
+use crate::outcome::{panic_message, CatchUnwind, TestOutcome};
+
+/// The environment variable that disables the `#[retry]` attributes if set to `0`: every test
+/// runs just once. It's read when the test runs.
+pub const ENV: &str = "RSTEST_RETRY";
+
+/// The environment variable with the path of the file where to append the tests that passed
+/// only after some failed attempts.
+pub const FLAKY_REPORT_ENV: &str = "RSTEST_FLAKY_REPORT";
+
+/// The retries to use for a test marked by `#[retry(retries)]`: `RSTEST_RETRY=0` disables
+/// them.
+fn retries(retries: u32) -> u32 {
+    match std::env::var(ENV) {
+        Ok(value) if value.trim() == "0" => 0,
+        _ => retries,
+    }
+}
+
+/// Run the test code and rerun it, up to `retries` times, while it fails: every failure is
+/// printed and the test passes if any attempt passes.
+pub fn run<T: TestOutcome>(test: &str, retries: u32, mut code: impl FnMut() -> T) -> T {
+    let attempts = self::retries(retries) + 1;
+    let mut attempt = 1;
+    loop {
+        let result = catch_unwind(AssertUnwindSafe(&mut code));
+        match check(test, attempt, attempts, result) {
+            Ok(out) => return out,
+            Err(()) => attempt += 1,
+        }
+    }
+}
+
+/// Like `run()` but for async test code.
+pub async fn run_async<T: TestOutcome, Fut: Future<Output = T>>(
+    test: &str,
+    retries: u32,
+    mut code: impl FnMut() -> Fut,
+) -> T {
+    let attempts = self::retries(retries) + 1;
+    let mut attempt = 1;
+    loop {
+        let result = CatchUnwind(Box::pin(code())).await;
+        match check(test, attempt, attempts, result) {
+            Ok(out) => return out,
+            Err(()) => attempt += 1,
+        }
+    }
+}
+
+/// Return the attempt outcome if it's the last one or if it passed, `Err(())` if the test
+/// should run again.
+fn check<T: TestOutcome>(
+    test: &str,
+    attempt: u32,
+    attempts: u32,
+    result: std::thread::Result<T>,
+) -> Result<T, ()> {
+    let failure = match &result {
+        Ok(out) => out.failure(),
+        Err(payload) => Some(panic_message(payload.as_ref())),
+    };
+    match failure {
+        None if attempt > 1 => flaky(test, attempt, attempts),
+        None => {}
+        Some(failure) if attempt < attempts => {
+            warn(&format!(
+                "*** RETRY {test}: attempt {attempt} of {attempts} failed: {failure} ***"
+            ));
+            return Err(());
+        }
+        Some(_) => {}
+    }
+    Ok(result.unwrap_or_else(|payload| resume_unwind(payload)))
+}
+
+/// The message is written directly to the standard error to avoid the test harness capture,
+/// and appended to the `RSTEST_FLAKY_REPORT` file if any.
+fn flaky(test: &str, attempt: u32, attempts: u32) {
+    warn(&format!(
+        "*** FLAKY TEST {test}: passed at attempt {attempt} of {attempts} ***"
+    ));
+    if let Some(path) = std::env::var_os(FLAKY_REPORT_ENV) {
+        let appended = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{test}\t{attempt}"));
+        if let Err(e) = appended {
+            warn(&format!(
+                "Cannot append flaky test to {}: {e}",
+                path.to_string_lossy()
+            ));
+        }
+    }
+}
+
+fn warn(message: &str) {
+    let _ = writeln!(std::io::stderr().lock(), "{message}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_run_again_until_pass() {
+        let mut attempts = 0;
+
+        let out = run("test", 3, || {
+            attempts += 1;
+            if attempts < 3 {
+                Err("boom")
+            } else {
+                Ok(attempts)
+            }
+        });
+
+        assert_eq!(Ok(3), out);
+    }
+
+    #[test]
+    fn should_return_last_failure() {
+        let mut attempts = 0;
+
+        let out = run("test", 2, || {
+            attempts += 1;
+            Err::<(), _>(attempts)
+        });
+
+        assert_eq!(Err(3), out);
+    }
+
+    #[test]
+    #[should_panic(expected = "boom 2")]
+    fn should_resume_last_panic() {
+        let mut attempts = 0;
+
+        run("test", 1, || {
+            attempts += 1;
+            panic!("boom {attempts}")
+        })
+    }
+
+    #[async_std::test]
+    async fn should_run_async_code_again_until_pass() {
+        let mut attempts = 0;
+
+        run_async("test", 1, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                async_std::task::yield_now().await;
+                assert_eq!(2, attempt);
+            }
+        })
+        .await;
+
+        assert_eq!(2, attempts);
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+/// The holders of the locks: tests marked by `#[rstest::serial(key)]` hold their keys
+/// exclusively and tests marked by `#[rstest::parallel(key)]` share them.
+struct Locks {
+    keys: BTreeMap<&'static str, Holders>,
+    wakers: Vec<Waker>,
+}
+
+#[derive(Default)]
+struct Holders {
+    exclusive: bool,
+    shared: usize,
+}
+
+static LOCKS: Mutex<Locks> = Mutex::new(Locks {
+    keys: BTreeMap::new(),
+    wakers: Vec::new(),
+});
+static RELEASED: Condvar = Condvar::new();
+
+fn locks() -> MutexGuard<'static, Locks> {
+    LOCKS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Locks {
+    /// Take all the keys or none of them: tests waiting for more keys cannot deadlock.
+    fn try_acquire(&mut self, keys: &'static [(&'static str, bool)]) -> bool {
+        let free = keys
+            .iter()
+            .all(|(key, exclusive)| match self.keys.get(key) {
+                None => true,
+                Some(holders) if *exclusive => !holders.exclusive && holders.shared == 0,
+                Some(holders) => !holders.exclusive,
+            });
+        if free {
+            for &(key, exclusive) in keys {
+                let holders = self.keys.entry(key).or_default();
+                if exclusive {
+                    holders.exclusive = true;
+                } else {
+                    holders.shared += 1;
+                }
+            }
+        }
+        free
+    }
+
+    fn release(&mut self, keys: &'static [(&'static str, bool)]) {
+        for (key, exclusive) in keys {
+            if let Some(holders) = self.keys.get_mut(key) {
+                if *exclusive {
+                    holders.exclusive = false;
+                } else {
+                    holders.shared -= 1;
+                }
+                if !holders.exclusive && holders.shared == 0 {
+                    self.keys.remove(key);
+                }
+            }
+        }
+        RELEASED.notify_all();
+        self.wakers.drain(..).for_each(Waker::wake);
+    }
+}
+
+/// Hold the keys of a test till the end of the test: fixtures teardown included.
+#[must_use]
+pub struct Guard(&'static [(&'static str, bool)]);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        locks().release(self.0)
+    }
+}
+
+/// Wait for the test `keys`: each key is taken exclusively if its flag is `true` and shared
+/// otherwise. `#[rstest::serial]` and `#[rstest::parallel]` without keys use the empty key.
+pub fn lock(keys: &'static [(&'static str, bool)]) -> Guard {
+    let mut locks = locks();
+    while !locks.try_acquire(keys) {
+        locks = RELEASED.wait(locks).unwrap_or_else(PoisonError::into_inner);
+    }
+    Guard(keys)
+}
+
+/// Like `lock()` but doesn't block the async executor while waiting.
+pub fn lock_async(keys: &'static [(&'static str, bool)]) -> impl Future<Output = Guard> {
+    Acquire(keys)
+}
+
+struct Acquire(&'static [(&'static str, bool)]);
+
+impl Future for Acquire {
+    type Output = Guard;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut locks = locks();
+        if locks.try_acquire(self.0) {
+            Poll::Ready(Guard(self.0))
+        } else {
+            locks.wakers.push(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_free(key: &str) -> bool {
+        !locks().keys.contains_key(key)
+    }
+
+    #[test]
+    fn should_share_parallel_keys() {
+        let first = lock(&[("test_shared", false)]);
+        let second = lock(&[("test_shared", false)]);
+
+        assert!(!locks().try_acquire(&[("test_shared", true)]));
+        drop((first, second));
+        assert!(is_free("test_shared"));
+    }
+
+    #[test]
+    fn should_take_all_keys_or_none() {
+        let _guard = lock(&[("test_b", true)]);
+
+        assert!(!locks().try_acquire(&[("test_a", true), ("test_b", true)]));
+        assert!(is_free("test_a"));
+    }
+
+    #[test]
+    fn should_wait_serial_keys() {
+        let guard = lock(&[("test_exclusive", true)]);
+        let waiting = std::thread::spawn(|| {
+            let _guard = lock(&[("test_exclusive", true)]);
+        });
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(!waiting.is_finished());
+        drop(guard);
+        waiting.join().unwrap();
+        assert!(is_free("test_exclusive"));
+    }
+
+    #[async_std::test]
+    async fn should_wait_serial_keys_without_blocking() {
+        let guard = lock(&[("test_async", true)]);
+        let waiting = async_std::task::spawn(async {
+            let _guard = lock_async(&[("test_async", true)]).await;
+        });
+
+        async_std::task::sleep(std::time::Duration::from_millis(20)).await;
+        drop(guard);
+        waiting.await;
+        assert!(is_free("test_async"));
+    }
+}
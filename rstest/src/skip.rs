@@ -0,0 +1,20 @@
+use std::io::Write;
+
+/// Report that the current test was skipped. The message is written directly to the
+/// standard error to avoid the test harness capture.
+pub fn report(reason: &str) {
+    crate::report::skip(reason);
+    let thread = std::thread::current();
+    let name = thread.name().unwrap_or("<unnamed>");
+    let _ = writeln!(std::io::stderr().lock(), "SKIPPED {name}: {reason}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_without_panic() {
+        report("some reason");
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use crate::timeout::parse_duration;
+
+/// The environment variable that sets the slow threshold of the tests without a
+/// `#[warn_slow]` attribute. It's read when the test runs.
+pub const DEFAULT_THRESHOLD_ENV: &str = "RSTEST_WARN_SLOW";
+
+/// The environment variable with the path of the file where to append the slow tests summary.
+pub const REPORT_ENV: &str = "RSTEST_SLOW_REPORT";
+
+/// The default threshold from the `RSTEST_WARN_SLOW` environment variable, if any.
+pub fn default_threshold() -> Option<Duration> {
+    std::env::var(DEFAULT_THRESHOLD_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            parse_duration(&value)
+                .unwrap_or_else(|e| panic!("Invalid {DEFAULT_THRESHOLD_ENV} '{value}': {e}"))
+        })
+}
+
+/// Measure the test execution: when dropped it warns if the test passed but took more than
+/// the threshold. A test that panics or calls `failed()` doesn't warn.
+pub struct Stopwatch {
+    test: String,
+    threshold: Option<Duration>,
+    start: Instant,
+}
+
+impl Stopwatch {
+    pub fn start(test: String, threshold: Option<Duration>) -> Self {
+        Self {
+            test,
+            threshold,
+            start: Instant::now(),
+        }
+    }
+
+    /// The test failed: don't warn.
+    pub fn failed(mut self) {
+        self.threshold = None;
+    }
+}
+
+impl Drop for Stopwatch {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        match self.threshold {
+            Some(threshold) if elapsed > threshold && !std::thread::panicking() => {
+                warn(&self.test, elapsed, threshold)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The warning is written directly to the standard error to avoid the test harness capture,
+/// and appended to the `RSTEST_SLOW_REPORT` file if any.
+fn warn(test: &str, elapsed: Duration, threshold: Duration) {
+    let _ = writeln!(
+        std::io::stderr().lock(),
+        "*** SLOW TEST {test}: took {elapsed:?} (threshold {threshold:?}) ***"
+    );
+    if let Some(path) = std::env::var_os(REPORT_ENV) {
+        let appended = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{}", summary_line(test, elapsed, threshold)));
+        if let Err(e) = appended {
+            let _ = writeln!(
+                std::io::stderr().lock(),
+                "Cannot append slow test summary to {}: {e}",
+                path.to_string_lossy()
+            );
+        }
+    }
+}
+
+/// A tab separated line with test name, elapsed and threshold milliseconds.
+fn summary_line(test: &str, elapsed: Duration, threshold: Duration) -> String {
+    format!("{test}\t{}\t{}", elapsed.as_millis(), threshold.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_format_summary_line() {
+        assert_eq!(
+            "a::case_1\t250\t200",
+            summary_line(
+                "a::case_1",
+                Duration::from_micros(250_300),
+                Duration::from_millis(200)
+            )
+        );
+    }
+
+    #[test]
+    fn should_not_warn_without_threshold() {
+        let _stopwatch = Stopwatch::start("test".to_owned(), None);
+    }
+
+    #[test]
+    fn should_not_warn_failed_tests() {
+        let stopwatch = Stopwatch::start("test".to_owned(), Some(Duration::ZERO));
+        std::thread::sleep(Duration::from_millis(1));
+
+        stopwatch.failed();
+    }
+}
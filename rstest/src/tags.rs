@@ -0,0 +1,48 @@
+/// The environment variable with the tags expression that selects the tests to run: a comma
+/// separated list of tags, where `!tag` excludes the tests with that tag (i.e. `db,!slow`). A
+/// test runs if it has at least one of the required tags (if any) and none of the excluded
+/// ones. It's read when the test runs.
+pub const ENV: &str = "RSTEST_TAGS";
+
+/// The skip reason if the test tags don't match the `RSTEST_TAGS` expression, `None` if the test
+/// should run.
+pub fn check(tags: &[&str]) -> Option<String> {
+    let expression = std::env::var(ENV).ok()?;
+    (!selected(&expression, tags)).then(|| {
+        let tags = match tags {
+            [] => "no tags".to_owned(),
+            tags => format!("tags [{}]", tags.join(", ")),
+        };
+        format!("{tags} don't match {ENV}=\"{expression}\"")
+    })
+}
+
+fn selected(expression: &str, tags: &[&str]) -> bool {
+    let (excluded, required): (Vec<_>, Vec<_>) = expression
+        .split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+        .partition(|term| term.starts_with('!'));
+    let has = |tag: &str| tags.contains(&tag.trim());
+    (required.is_empty() || required.iter().any(|&tag| has(tag)))
+        && !excluded.iter().any(|tag| has(&tag[1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_select_by_tags_expression() {
+        assert!(selected("", &[]));
+        assert!(selected("db", &["db", "slow"]));
+        assert!(selected("db, net", &["net"]));
+        assert!(selected("!slow", &["db"]));
+        assert!(selected("!slow", &[]));
+        assert!(selected(" db , ! slow ", &["db"]));
+        assert!(!selected("db", &[]));
+        assert!(!selected("db", &["net"]));
+        assert!(!selected("db,!slow", &["db", "slow"]));
+        assert!(!selected("!slow", &["slow"]));
+    }
+}
@@ -1,21 +1,94 @@
-use std::{sync::mpsc, thread, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    io::Write,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
 
 #[cfg(feature = "async-timeout")]
 use futures::{select, Future, FutureExt};
 #[cfg(feature = "async-timeout")]
 use futures_timer::Delay;
+#[cfg(feature = "async-timeout")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The phase of the test execution: reported when the timeout expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Resolving the fixture or the value of the given argument
+    Fixture(&'static str),
+    /// Awaiting the `#[future(awt)]` arguments
+    Await,
+    /// Running the test body
+    Body,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixture(name) => write!(f, "resolving fixture `{name}`"),
+            Self::Await => write!(f, "awaiting `#[future(awt)]` arguments"),
+            Self::Body => write!(f, "running the test body"),
+        }
+    }
+}
+
+type PhaseTracker = Arc<Mutex<Option<Phase>>>;
+
+thread_local! {
+    static CURRENT_PHASE: RefCell<Option<PhaseTracker>> = const { RefCell::new(None) };
+}
+
+/// Record the phase of the test that is running on the current thread, if it has a timeout.
+pub fn enter_phase(phase: Phase) {
+    CURRENT_PHASE.with(|current| {
+        if let Some(tracker) = current.borrow().as_ref() {
+            *tracker.lock().unwrap_or_else(|e| e.into_inner()) = Some(phase);
+        }
+    })
+}
+
+/// Run `code` tracking its phases in `tracker`.
+fn tracked<T>(tracker: &PhaseTracker, code: impl FnOnce() -> T) -> T {
+    struct Restore(Option<PhaseTracker>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT_PHASE.with(|current| current.replace(self.0.take()));
+        }
+    }
+    let _restore = Restore(CURRENT_PHASE.with(|current| current.replace(Some(tracker.clone()))));
+    code()
+}
+
+fn expired_message(timeout: Duration, tracker: &PhaseTracker) -> String {
+    match *tracker.lock().unwrap_or_else(|e| e.into_inner()) {
+        Some(phase) => format!("Timeout {timeout:?} expired while {phase}"),
+        None => format!("Timeout {timeout:?} expired"),
+    }
+}
 
 pub fn execute_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 'static>(
     code: F,
     timeout: Duration,
 ) -> T {
+    let tracker = PhaseTracker::default();
     let (sender, receiver) = mpsc::channel();
     let thread = if let Some(name) = thread::current().name() {
         thread::Builder::new().name(name.to_string())
     } else {
         thread::Builder::new()
     };
-    let handle = thread.spawn(move || sender.send(code())).unwrap();
+    let handle = {
+        let tracker = tracker.clone();
+        thread
+            .spawn(move || sender.send(tracked(&tracker, code)))
+            .unwrap()
+    };
     match receiver.recv_timeout(timeout) {
         Ok(result) => {
             // Unwraps are safe because we got a result from the thread, which is not a `SendError`,
@@ -23,7 +96,7 @@ pub fn execute_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 's
             handle.join().unwrap().unwrap();
             result
         }
-        Err(mpsc::RecvTimeoutError::Timeout) => panic!("Timeout {:?} expired", timeout),
+        Err(mpsc::RecvTimeoutError::Timeout) => panic!("{}", expired_message(timeout, &tracker)),
         Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
             Err(any) => std::panic::resume_unwind(any),
             Ok(_) => unreachable!(),
@@ -31,16 +104,248 @@ pub fn execute_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 's
     }
 }
 
+/// The environment variable that sets the timeout of the tests without a `#[timeout]`
+/// attribute. It's read when the test runs.
+pub const DEFAULT_TIMEOUT_ENV: &str = "RSTEST_TIMEOUT";
+
+/// The default timeout from the `RSTEST_TIMEOUT` environment variable, if any.
+pub fn default_timeout() -> Option<Duration> {
+    std::env::var(DEFAULT_TIMEOUT_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            parse_duration(&value)
+                .unwrap_or_else(|e| panic!("Invalid {DEFAULT_TIMEOUT_ENV} '{value}': {e}"))
+        })
+}
+
+/// Run the test code with the default timeout, if any, on another thread like
+/// `execute_with_timeout_sync()`.
+pub fn execute_with_default_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 'static>(
+    code: F,
+) -> T {
+    match default_timeout() {
+        Some(timeout) => execute_with_timeout_sync(code, timeout),
+        None => code(),
+    }
+}
+
+/// The sync test code that should run with the default timeout: the generated code calls
+/// `(&&&DefaultTimeout::new(code)).execute_with_default_timeout()` to run it on another
+/// thread if its output is `Send` and `'static`, and on the current thread without timeout
+/// otherwise. The dispatch cannot look at what the closure captures: the generated closure
+/// resolves all the test arguments inside it and captures just `Send` values.
+pub struct DefaultTimeout<F>(Cell<Option<F>>);
+
+impl<F> DefaultTimeout<F> {
+    pub fn new(code: F) -> Self {
+        Self(Cell::new(Some(code)))
+    }
+
+    fn take(&self) -> F {
+        self.0
+            .take()
+            .expect("Test code should be executed just once")
+    }
+}
+
+pub trait ViaThread {
+    type Output;
+    fn execute_with_default_timeout(&self) -> Self::Output;
+}
+
+impl<T, F> ViaThread for &&DefaultTimeout<F>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    type Output = T;
+    fn execute_with_default_timeout(&self) -> Self::Output {
+        execute_with_default_timeout_sync(self.take())
+    }
+}
+
+pub trait WithoutTimeout {
+    type Output;
+    fn execute_with_default_timeout(&self) -> Self::Output;
+}
+
+impl<T, F: FnOnce() -> T> WithoutTimeout for &DefaultTimeout<F> {
+    type Output = T;
+    fn execute_with_default_timeout(&self) -> Self::Output {
+        if let Some(timeout) = default_timeout() {
+            let _ = writeln!(
+                std::io::stderr().lock(),
+                "{}: {DEFAULT_TIMEOUT_ENV} {timeout:?} ignored: the test output is not `Send` \
+                 and `'static`, use `#[timeout(<duration>, watchdog)]` instead",
+                thread::current().name().unwrap_or("<unnamed>")
+            );
+        }
+        self.take()()
+    }
+}
+
+/// Run the async test code with the default timeout, if any.
+#[cfg(feature = "async-timeout")]
+pub async fn execute_with_default_timeout_async<T, Fut: Future<Output = T>, F: FnOnce() -> Fut>(
+    code: F,
+) -> T {
+    match default_timeout() {
+        Some(timeout) => execute_with_timeout_async(code, timeout).await,
+        None => code().await,
+    }
+}
+
+/// Parse a duration like `500ms`, `1.5s`, `2m` or `1h`: a number without unit is in seconds.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_start);
+    let amount = amount
+        .parse::<f64>()
+        .map_err(|_| "expected a number followed by an optional unit".to_owned())?;
+    let seconds = match unit.trim() {
+        "ms" => 0.001,
+        "" | "s" => 1.0,
+        "m" | "min" => 60.0,
+        "h" => 3600.0,
+        unit => return Err(format!("unknown unit '{unit}': use one of ms, s, m or h")),
+    };
+    Duration::try_from_secs_f64(amount * seconds).map_err(|e| e.to_string())
+}
+
+/// How long the watchdog waits for a checkpoint after the timeout is expired before aborting
+/// the test process.
+pub const WATCHDOG_ABORT_GRACE: Duration = Duration::from_secs(1);
+
+thread_local! {
+    static CURRENT_WATCHDOG: RefCell<Option<Arc<WatchdogState>>> = const { RefCell::new(None) };
+}
+
+/// Run the test code on the current thread under a watchdog: the code doesn't need to be
+/// `Send` or `'static`. When the timeout expires the watchdog reports it, and the test
+/// unwinds at the next `checkpoint()` or when the code returns. If no checkpoint is reached
+/// in `WATCHDOG_ABORT_GRACE` the watchdog aborts the test process.
+pub fn execute_with_watchdog_sync<T, F: FnOnce() -> T>(code: F, timeout: Duration) -> T {
+    let watchdog = Watchdog::start(timeout);
+    let result = tracked(&watchdog.state.tracker, code);
+    watchdog.state.check();
+    result
+}
+
+/// Panics if the timeout of the test running on the current thread is expired. Call it in
+/// long running code of tests that use a watchdog timeout to fail instead of being aborted.
+pub fn checkpoint() {
+    CURRENT_WATCHDOG.with(|current| {
+        if let Some(state) = current.borrow().as_ref() {
+            state.check()
+        }
+    })
+}
+
+struct WatchdogState {
+    tracker: PhaseTracker,
+    /// The expired message, if the timeout is expired
+    expired: Mutex<Option<String>>,
+}
+
+impl WatchdogState {
+    fn check(&self) {
+        let expired = self
+            .expired
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if let Some(message) = expired {
+            panic!("{message}")
+        }
+    }
+}
+
+struct Watchdog {
+    state: Arc<WatchdogState>,
+    done: Option<mpsc::Sender<()>>,
+    handle: Option<thread::JoinHandle<()>>,
+    previous: Option<Arc<WatchdogState>>,
+}
+
+impl Watchdog {
+    fn start(timeout: Duration) -> Self {
+        let state = Arc::new(WatchdogState {
+            tracker: Default::default(),
+            expired: Default::default(),
+        });
+        let (done, receiver) = mpsc::channel::<()>();
+        let name = thread::current().name().unwrap_or("<unnamed>").to_owned();
+        let watched = state.clone();
+        let handle = thread::spawn(move || {
+            if let Err(mpsc::RecvTimeoutError::Timeout) = receiver.recv_timeout(timeout) {
+                let message = expired_message(timeout, &watched.tracker);
+                report(&format!("{name}: {message}"));
+                *watched.expired.lock().unwrap_or_else(|e| e.into_inner()) = Some(message);
+                if let Err(mpsc::RecvTimeoutError::Timeout) =
+                    receiver.recv_timeout(WATCHDOG_ABORT_GRACE)
+                {
+                    report(&format!(
+                        "{name}: no checkpoint reached in {WATCHDOG_ABORT_GRACE:?}: abort"
+                    ));
+                    std::process::abort();
+                }
+            }
+        });
+        let previous = CURRENT_WATCHDOG.with(|current| current.replace(Some(state.clone())));
+        Self {
+            state,
+            done: Some(done),
+            handle: Some(handle),
+            previous,
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        CURRENT_WATCHDOG.with(|current| current.replace(self.previous.take()));
+        // Disconnect the watchdog thread to stop it
+        self.done.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// The message is written directly to the standard error to avoid the test harness capture.
+fn report(message: &str) {
+    let _ = writeln!(std::io::stderr().lock(), "WATCHDOG {message}");
+}
+
 #[cfg(feature = "async-timeout")]
 pub async fn execute_with_timeout_async<T, Fut: Future<Output = T>, F: FnOnce() -> Fut>(
     code: F,
     timeout: Duration,
 ) -> T {
+    let tracker = PhaseTracker::default();
     select! {
         () = async {
             Delay::new(timeout).await;
-        }.fuse() => panic!("Timeout {:?} expired", timeout),
-        out = code().fuse() => out,
+        }.fuse() => panic!("{}", expired_message(timeout, &tracker)),
+        out = Tracked(tracker.clone(), Box::pin(code())).fuse() => out,
+    }
+}
+
+/// Track the phases of the wrapped future: it could be polled on different threads.
+#[cfg(feature = "async-timeout")]
+struct Tracked<Fut>(PhaseTracker, Pin<Box<Fut>>);
+
+#[cfg(feature = "async-timeout")]
+impl<Fut: Future> Future for Tracked<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self(tracker, fut) = &mut *self;
+        tracked(tracker, || fut.as_mut().poll(cx))
     }
 }
 
@@ -76,6 +381,19 @@ mod tests {
                 .await
             }
 
+            #[async_std::test]
+            #[should_panic = "Timeout 10ms expired while awaiting `#[future(awt)]` arguments"]
+            async fn should_report_the_phase() {
+                execute_with_timeout_async(
+                    || async {
+                        enter_phase(Phase::Await);
+                        test(Duration::from_millis(40)).await
+                    },
+                    Duration::from_millis(10),
+                )
+                .await
+            }
+
             #[async_std::test]
             async fn should_pass() {
                 execute_with_timeout_async(
@@ -145,6 +463,131 @@ mod tests {
         }
     }
 
+    mod default_timeout {
+        use super::*;
+
+        #[test]
+        fn should_parse_durations() {
+            assert_eq!(Ok(Duration::from_secs(2)), parse_duration("2"));
+            assert_eq!(Ok(Duration::from_millis(500)), parse_duration("500ms"));
+            assert_eq!(Ok(Duration::from_millis(1500)), parse_duration("1.5s"));
+            assert_eq!(Ok(Duration::from_secs(120)), parse_duration("2m"));
+            assert_eq!(Ok(Duration::from_secs(3600)), parse_duration(" 1h "));
+        }
+
+        #[test]
+        fn should_report_invalid_durations() {
+            assert!(parse_duration("ms")
+                .unwrap_err()
+                .contains("expected a number"));
+            assert!(parse_duration("2d")
+                .unwrap_err()
+                .contains("unknown unit 'd'"));
+        }
+
+        #[test]
+        fn should_report_too_long_durations_without_panic() {
+            assert!(parse_duration(&"9".repeat(400)).is_err());
+            assert!(parse_duration(&format!("{}h", "9".repeat(20))).is_err());
+            assert!(parse_duration("1e300").is_err());
+            assert!(parse_duration("inf").is_err());
+        }
+
+        #[test]
+        fn should_execute_also_code_that_is_not_send() {
+            assert_eq!(
+                std::rc::Rc::new(42),
+                (&&&DefaultTimeout::new(|| std::rc::Rc::new(42))).execute_with_default_timeout()
+            );
+            assert_eq!(
+                "send",
+                (&&&DefaultTimeout::new(|| "send")).execute_with_default_timeout()
+            );
+        }
+
+        #[test]
+        fn should_dispatch_send_code_via_thread_whatever_it_returns() {
+            assert_eq!(
+                "send",
+                <&&DefaultTimeout<_> as ViaThread>::execute_with_default_timeout(
+                    &&&DefaultTimeout::new(|| "send")
+                )
+            );
+            assert_eq!(
+                Ok::<(), String>(()),
+                <&&DefaultTimeout<_> as ViaThread>::execute_with_default_timeout(
+                    &&&DefaultTimeout::new(|| Ok::<(), String>(()))
+                )
+            );
+        }
+    }
+
+    mod watchdog_version {
+        use super::*;
+        use std::rc::Rc;
+
+        #[test]
+        fn should_pass_and_run_on_the_current_thread() {
+            let data = Rc::new(vec![1, 2, 3]);
+            let borrowed = &data;
+            let thread = thread::current().id();
+
+            let result = execute_with_watchdog_sync(
+                || {
+                    assert_eq!(thread, thread::current().id());
+                    borrowed.len()
+                },
+                Duration::from_millis(70),
+            );
+
+            assert_eq!(3, result);
+        }
+
+        #[test]
+        #[should_panic = "inner message"]
+        fn should_fail_for_panic_with_right_panic_message() {
+            execute_with_watchdog_sync(|| panic!("inner message"), Duration::from_millis(30))
+        }
+
+        #[test]
+        #[should_panic = "Timeout 30ms expired"]
+        fn should_fail_when_return_after_timeout() {
+            execute_with_watchdog_sync(
+                || thread::sleep(Duration::from_millis(70)),
+                Duration::from_millis(30),
+            )
+        }
+
+        #[test]
+        #[should_panic = "Timeout 30ms expired while running the test body"]
+        fn should_report_the_phase() {
+            execute_with_watchdog_sync(
+                || {
+                    enter_phase(Phase::Body);
+                    thread::sleep(Duration::from_millis(70))
+                },
+                Duration::from_millis(30),
+            )
+        }
+
+        #[test]
+        #[should_panic = "Timeout 30ms expired"]
+        fn should_unwind_at_checkpoint() {
+            execute_with_watchdog_sync(
+                || loop {
+                    thread::sleep(Duration::from_millis(5));
+                    checkpoint();
+                },
+                Duration::from_millis(30),
+            )
+        }
+
+        #[test]
+        fn checkpoint_should_do_nothing_without_watchdog() {
+            checkpoint();
+        }
+    }
+
     mod thread_version {
         use super::*;
 
@@ -185,6 +628,23 @@ mod tests {
                 Duration::from_millis(30),
             )
         }
+
+        #[test]
+        #[should_panic = "Timeout 30ms expired while resolving fixture `db`"]
+        fn should_report_the_phase() {
+            execute_with_timeout_sync(
+                || {
+                    enter_phase(Phase::Fixture("db"));
+                    test(Duration::from_millis(70))
+                },
+                Duration::from_millis(30),
+            )
+        }
+
+        #[test]
+        fn enter_phase_should_do_nothing_without_timeout() {
+            enter_phase(Phase::Body);
+        }
         #[test]
         fn should_compile_also_with_no_copy_move() {
             struct S {}
@@ -0,0 +1,490 @@
+use std::{
+    cell::Cell,
+    fmt::{Debug, Display},
+};
+
+use crate::outcome::TestOutcome;
+
+/// The environment variable that enables the pretty print (`{:#?}`) of the traced arguments
+/// (any value but `0`). It's read when the test runs.
+pub const PRETTY_ENV: &str = "RSTEST_TRACE_PRETTY";
+
+/// The environment variable with the max number of chars of a traced argument: longer
+/// values are truncated. It's read when the test runs.
+pub const MAX_LEN_ENV: &str = "RSTEST_TRACE_MAX_LEN";
+
+fn pretty() -> bool {
+    std::env::var(PRETTY_ENV).map_or(false, |pretty| pretty != "0")
+}
+
+fn max_len() -> Option<usize> {
+    std::env::var(MAX_LEN_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .unwrap_or_else(|e| panic!("Invalid {MAX_LEN_ENV} '{value}': {e}"))
+        })
+}
+
+fn truncate(value: String, max_len: Option<usize>) -> String {
+    match max_len {
+        Some(max_len) if value.chars().count() > max_len => {
+            let more = value.chars().count() - max_len;
+            let head = value.chars().take(max_len).collect::<String>();
+            format!("{head}... ({more} more chars)")
+        }
+        _ => value,
+    }
+}
+
+/// Wrap a traced argument to format it with the best available trait (autoref
+/// specialization like `Magic<T>`): `Debug` if implemented, `Display` otherwise, and just
+/// its type name as last resort.
+pub struct Printable<'a, T>(pub &'a T);
+
+pub trait ViaDebug {
+    fn trace_format(&self) -> String;
+}
+
+impl<T: Debug> ViaDebug for &&Printable<'_, T> {
+    fn trace_format(&self) -> String {
+        if pretty() {
+            format!("{:#?}", self.0)
+        } else {
+            format!("{:?}", self.0)
+        }
+    }
+}
+
+pub trait ViaDisplay {
+    fn trace_format(&self) -> String;
+}
+
+impl<T: Display> ViaDisplay for &Printable<'_, T> {
+    fn trace_format(&self) -> String {
+        format!("{}", self.0)
+    }
+}
+
+pub trait ViaTypeName {
+    fn trace_format(&self) -> String;
+}
+
+impl<T> ViaTypeName for Printable<'_, T> {
+    fn trace_format(&self) -> String {
+        format!("{} (unprintable)", std::any::type_name::<T>())
+    }
+}
+
+/// The traced arguments of a test. They are printed on the standard output by default, or
+/// emitted as `tracing` events or `log` records (one for each argument, with `test`, `case`
+/// and `argument` fields) if the `tracing` or `log` feature is enabled.
+pub struct Arguments {
+    test: String,
+    #[cfg_attr(not(any(feature = "log", feature = "tracing")), allow(dead_code))]
+    case: Option<String>,
+    arguments: Vec<(&'static str, String)>,
+}
+
+impl Arguments {
+    /// `test` is the name reported by the test harness and `function` the name of the test
+    /// function: the case is the part of the test name that follows it.
+    pub fn new(test: String, function: &str, arguments: Vec<(&'static str, String)>) -> Self {
+        let max_len = max_len();
+        Self {
+            case: case_name(&test, function),
+            test,
+            arguments: arguments
+                .into_iter()
+                .map(|(name, value)| (name, truncate(value, max_len)))
+                .collect(),
+        }
+    }
+
+    /// Trace the arguments before the test starts.
+    pub fn trace(&self) {
+        self.emit(false)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn emit(&self, failed: bool) {
+        for (argument, value) in &self.arguments {
+            tracing::info!(
+                target: "rstest::trace",
+                test = self.test.as_str(),
+                case = self.case.as_deref(),
+                argument = *argument,
+                value = value.as_str(),
+                failed,
+                "{argument} = {value}"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    fn emit(&self, failed: bool) {
+        for (argument, value) in &self.arguments {
+            log::info!(
+                target: "rstest::trace",
+                test = self.test.as_str(),
+                case = self.case.as_deref(),
+                argument = *argument,
+                value = value.as_str(),
+                failed = failed;
+                "{argument} = {value}"
+            );
+        }
+    }
+
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    fn emit(&self, failed: bool) {
+        println!("{}", self.report(failed));
+    }
+
+    #[cfg_attr(any(feature = "log", feature = "tracing"), allow(dead_code))]
+    fn report(&self, failed: bool) -> String {
+        let (start, end) = if failed {
+            (format!(" TEST ARGUMENTS {} ", self.test), " TEST FAILED ")
+        } else {
+            (" TEST ARGUMENTS ".to_owned(), " TEST START ")
+        };
+        let mut lines = vec![format!("{start:-^40}")];
+        lines.extend(
+            self.arguments
+                .iter()
+                .map(|(name, value)| format!("{name} = {value}")),
+        );
+        lines.push(format!("{end:-^40}"));
+        lines.join("\n")
+    }
+}
+
+fn case_name(test: &str, function: &str) -> Option<String> {
+    let segments = test.split("::").collect::<Vec<_>>();
+    segments
+        .iter()
+        .rposition(|&s| s == function)
+        .map(|position| segments[position + 1..].join("::"))
+        .filter(|case| !case.is_empty())
+}
+
+/// Hold the arguments of a test marked by `#[trace(on_failure)]` and trace them only if the
+/// test fails: a panic is checked when dropped and a failure outcome by `check()`.
+pub struct OnFailure(Arguments);
+
+impl OnFailure {
+    pub fn new(arguments: Arguments) -> Self {
+        Self(arguments)
+    }
+
+    /// Trace the arguments if the test returned a failure.
+    pub fn check<T: TestOutcome>(self, outcome: T) -> T {
+        if outcome.failure().is_some() {
+            self.0.emit(true);
+        }
+        outcome
+    }
+}
+
+impl Drop for OnFailure {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.emit(true);
+        }
+    }
+}
+
+thread_local! {
+    /// The nesting depth of the fixture resolutions if the test running on this thread traces
+    /// its fixtures.
+    static FIXTURES_DEPTH: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Trace the fixtures built by the current thread until dropped: used by tests marked by
+/// `#[trace(fixtures)]`.
+pub struct TraceFixtures(Option<usize>);
+
+impl TraceFixtures {
+    pub fn start() -> Self {
+        Self(FIXTURES_DEPTH.with(|depth| depth.replace(Some(0))))
+    }
+}
+
+impl Drop for TraceFixtures {
+    fn drop(&mut self) {
+        FIXTURES_DEPTH.with(|depth| depth.set(self.0))
+    }
+}
+
+/// The resolution of a fixture through its `default()` or `partial_n()` method: the fixtures
+/// resolved until it's dropped are nested in it.
+pub struct FixtureScope(bool);
+
+impl FixtureScope {
+    /// `path` is the path of the fixture (i.e. `module_path!()` and its name) and `method` the
+    /// method used to resolve it.
+    pub fn enter(path: &str, method: &str) -> Self {
+        FIXTURES_DEPTH.with(|depth| match depth.get() {
+            Some(d) => {
+                emit_fixture(d, &format!("resolve {path}::{method}"));
+                depth.set(Some(d + 1));
+                Self(true)
+            }
+            None => Self(false),
+        })
+    }
+}
+
+impl Drop for FixtureScope {
+    fn drop(&mut self) {
+        if self.0 {
+            FIXTURES_DEPTH.with(|depth| depth.set(depth.get().map(|d| d.saturating_sub(1))))
+        }
+    }
+}
+
+/// The formatted fixture arguments, only if the fixtures are traced.
+pub fn fixture_arguments(
+    arguments: impl FnOnce() -> Vec<(&'static str, String)>,
+) -> Option<Vec<(&'static str, String)>> {
+    FIXTURES_DEPTH
+        .with(|depth| depth.get().is_some())
+        .then(arguments)
+}
+
+/// Trace the value built by the fixture `name` from the given `arguments` (see
+/// `fixture_arguments()`) at the same depth of its resolution.
+pub fn fixture_built(
+    name: &str,
+    arguments: Option<Vec<(&'static str, String)>>,
+    value: impl FnOnce() -> String,
+) {
+    if let (Some(arguments), Some(depth)) = (arguments, FIXTURES_DEPTH.with(Cell::get)) {
+        let max_len = max_len();
+        let arguments = arguments
+            .into_iter()
+            .map(|(name, value)| format!("{name} = {}", truncate(value, max_len)))
+            .collect::<Vec<_>>();
+        emit_fixture(
+            depth.saturating_sub(1),
+            &format!(
+                "{name}({}) = {}",
+                arguments.join(", "),
+                truncate(value(), max_len)
+            ),
+        );
+    }
+}
+
+fn indent(depth: usize, line: &str) -> String {
+    format!("{}{line}", "    ".repeat(depth))
+}
+
+#[cfg(feature = "tracing")]
+fn emit_fixture(depth: usize, line: &str) {
+    tracing::info!(target: "rstest::trace", depth, "{}", indent(depth, line));
+}
+
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+fn emit_fixture(depth: usize, line: &str) {
+    log::info!(target: "rstest::trace", depth = depth; "{}", indent(depth, line));
+}
+
+#[cfg(not(any(feature = "log", feature = "tracing")))]
+fn emit_fixture(depth: usize, line: &str) {
+    println!("{}", indent(depth, line));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_format_via_debug() {
+        assert_eq!(r#""str""#, (&&&Printable(&"str")).trace_format());
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)]
+    fn should_format_via_display_if_not_debug() {
+        struct S;
+        impl Display for S {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "display S")
+            }
+        }
+
+        assert_eq!("display S", (&&&Printable(&S)).trace_format());
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)]
+    fn should_format_type_name_if_not_printable() {
+        struct S;
+
+        assert_eq!(
+            format!("{} (unprintable)", std::any::type_name::<S>()),
+            (&&&Printable(&S)).trace_format()
+        );
+    }
+
+    #[test]
+    fn should_truncate_long_values() {
+        assert_eq!("abcdef", truncate("abcdef".to_owned(), None));
+        assert_eq!("abcdef", truncate("abcdef".to_owned(), Some(6)));
+        assert_eq!(
+            "abcd... (2 more chars)",
+            truncate("abcdef".to_owned(), Some(4))
+        );
+        assert_eq!("àè... (1 more chars)", truncate("àèì".to_owned(), Some(2)));
+    }
+
+    #[test]
+    fn should_extract_case_name_from_test_name() {
+        assert_eq!(None, case_name("single", "single"));
+        assert_eq!(None, case_name("module::single", "single"));
+        assert_eq!(
+            Some("case_1".to_owned()),
+            case_name("module::cases::case_1", "cases")
+        );
+        assert_eq!(
+            Some("u_1_1::s_2_2".to_owned()),
+            case_name("matrix::matrix::u_1_1::s_2_2", "matrix")
+        );
+    }
+
+    #[test]
+    fn should_report_arguments_before_the_test_start() {
+        let arguments = Arguments::new(
+            "cases::case_1".to_owned(),
+            "cases",
+            vec![("a", "42".to_owned()), ("s", r#""str""#.to_owned())],
+        );
+
+        assert_eq!(
+            [
+                "------------ TEST ARGUMENTS ------------",
+                "a = 42",
+                r#"s = "str""#,
+                "-------------- TEST START --------------",
+            ]
+            .join("\n"),
+            arguments.report(false)
+        );
+    }
+
+    #[test]
+    fn should_report_test_name_and_arguments_on_failure() {
+        let arguments = Arguments::new(
+            "cases::case_1".to_owned(),
+            "cases",
+            vec![("a", "42".to_owned()), ("s", r#""str""#.to_owned())],
+        );
+
+        assert_eq!(
+            [
+                "----- TEST ARGUMENTS cases::case_1 -----",
+                "a = 42",
+                r#"s = "str""#,
+                "------------- TEST FAILED --------------",
+            ]
+            .join("\n"),
+            arguments.report(true)
+        );
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    #[test]
+    fn should_log_argument_name_and_value_as_fields() {
+        use log::kv::{Error, Key, Value, VisitSource};
+        use std::sync::Mutex;
+
+        struct Fields(Mutex<Vec<(String, String)>>);
+        struct Collect<'a>(&'a mut Vec<(String, String)>);
+
+        impl VisitSource<'_> for Collect<'_> {
+            fn visit_pair(&mut self, key: Key<'_>, value: Value<'_>) -> Result<(), Error> {
+                self.0.push((key.to_string(), value.to_string()));
+                Ok(())
+            }
+        }
+
+        impl log::Log for Fields {
+            fn enabled(&self, _metadata: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                if record.target() == "rstest::trace" {
+                    let _ = record
+                        .key_values()
+                        .visit(&mut Collect(&mut self.0.lock().unwrap()));
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        static FIELDS: Fields = Fields(Mutex::new(Vec::new()));
+        log::set_logger(&FIELDS).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+
+        Arguments::new(
+            "cases::case_1".to_owned(),
+            "cases",
+            vec![("a", "42".to_owned())],
+        )
+        .trace();
+
+        let fields = FIELDS.0.lock().unwrap();
+        assert!(fields.contains(&("argument".to_owned(), "a".to_owned())));
+        assert!(fields.contains(&("value".to_owned(), "42".to_owned())));
+    }
+
+    #[test]
+    fn should_indent_fixture_lines() {
+        assert_eq!("a", indent(0, "a"));
+        assert_eq!("        a", indent(2, "a"));
+    }
+
+    #[test]
+    fn should_format_fixture_arguments_only_if_traced() {
+        assert_eq!(None, fixture_arguments(|| vec![("a", "42".to_owned())]));
+
+        let _trace = TraceFixtures::start();
+
+        assert_eq!(
+            Some(vec![("a", "42".to_owned())]),
+            fixture_arguments(|| vec![("a", "42".to_owned())])
+        );
+    }
+
+    #[test]
+    fn should_track_fixture_resolution_depth() {
+        let depth = || FIXTURES_DEPTH.with(Cell::get);
+        {
+            let _ignored = FixtureScope::enter("crate::fix", "default");
+            assert_eq!(None, depth());
+        }
+        {
+            let _trace = TraceFixtures::start();
+            let _outer = FixtureScope::enter("crate::outer", "partial_1");
+            {
+                let _inner = FixtureScope::enter("crate::inner", "default");
+                assert_eq!(Some(2), depth());
+            }
+            assert_eq!(Some(1), depth());
+        }
+        assert_eq!(None, depth());
+    }
+
+    #[test]
+    fn should_return_the_outcome() {
+        let trace = OnFailure::new(Arguments::new("test".to_owned(), "test", vec![]));
+
+        assert_eq!(Err::<(), _>(42), trace.check(Err::<(), _>(42)));
+    }
+}
@@ -0,0 +1,41 @@
+/// Check the outcome of a test marked by `#[xfail]`: a failure is expected, so it passes. An
+/// unexpected pass fails the test in strict mode and just prints a warning otherwise.
+pub fn check(outcome: Result<(), String>, reason: &str, strict: bool) {
+    match outcome {
+        Err(failure) => println!("XFAIL: {}", message(reason, &failure)),
+        Ok(()) if strict => panic!("XPASS: {}", message(reason, "test passed unexpectedly")),
+        Ok(()) => eprintln!(
+            "warning: XPASS: {}",
+            message(reason, "test passed unexpectedly")
+        ),
+    }
+}
+
+fn message(reason: &str, description: &str) -> String {
+    if reason.is_empty() {
+        description.to_owned()
+    } else {
+        format!("{reason}: {description}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pass_on_expected_failure() {
+        check(Err("boom".to_owned()), "known bug", true);
+    }
+
+    #[test]
+    #[should_panic(expected = "XPASS: known bug: test passed unexpectedly")]
+    fn should_fail_on_unexpected_pass() {
+        check(Ok(()), "known bug", true);
+    }
+
+    #[test]
+    fn should_just_warn_on_unexpected_pass_in_non_strict_mode() {
+        check(Ok(()), "known bug", false);
+    }
+}
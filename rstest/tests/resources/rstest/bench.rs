@@ -0,0 +1,35 @@
+use rstest::*;
+use std::time::Duration;
+
+#[fixture]
+fn data() -> Vec<u32> {
+    // Fixtures are resolved for every call but not measured
+    std::thread::sleep(Duration::from_millis(5));
+    (0..1000).rev().collect()
+}
+
+#[rstest(bench)]
+#[case::small(10)]
+#[case::all(1000)]
+fn sort(data: Vec<u32>, #[case] len: usize) {
+    let mut values = data[..len].to_vec();
+    values.sort();
+    assert_eq!(len, values.len());
+}
+
+#[rstest(bench)]
+fn sum(#[values(10, 100)] n: u64) {
+    assert_eq!(n * (n + 1) / 2, (1..=n).sum::<u64>());
+}
+
+#[rstest(bench)]
+#[case::pass(true)]
+#[case::fail(false)]
+fn fail(#[case] pass: bool) -> Result<(), &'static str> {
+    pass.then_some(()).ok_or("failed")
+}
+
+#[rstest(bench)]
+async fn async_bench() {
+    async_std::task::yield_now().await;
+}
@@ -0,0 +1,26 @@
+use rstest::rstest;
+
+#[rstest]
+#[case(input = 2, expected = 4)]
+#[case(expected = 9, input = 3)]
+#[case(4, expected = 16)]
+#[case::fail(expected = 4, input = 3)]
+fn square(#[case] input: u32, #[case] expected: u32) {
+    assert_eq!(expected, input * input);
+}
+
+#[rstest(
+    s, len,
+    case(len = 4, s = "ciao"),
+    case(s = "hello", len = 5)
+)]
+fn strlen(s: &str, len: usize) {
+    assert_eq!(len, s.len());
+}
+
+#[rstest]
+#[case(a = 1, b = 2)]
+#[case(b = 1, a = 2)]
+fn matrix(#[case] a: u32, #[case] b: u32, #[values(3, 4)] c: u32) {
+    assert!(a < b && b < c);
+}
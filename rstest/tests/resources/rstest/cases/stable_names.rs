@@ -0,0 +1,26 @@
+use rstest::rstest;
+
+#[rstest(stable_names)]
+#[case(2, 4)]
+#[case::three(3, 9)]
+#[case(-1, 1)]
+#[case(4, 15)]
+fn square(#[case] input: i32, #[case] expected: i32) {
+    assert_eq!(expected, input * input);
+}
+
+#[rstest(
+    s, len,
+    case("ciao", 4),
+    case(String::from("hello"), 5)
+    ::stable_names
+)]
+fn strlen(s: impl AsRef<str>, len: usize) {
+    assert_eq!(len, s.as_ref().len());
+}
+
+#[rstest(stable_names)]
+#[case("a very long string that is used to check that too long names get shortened by an hash")]
+fn long(#[case] s: &str) {
+    assert!(s.len() > 64);
+}
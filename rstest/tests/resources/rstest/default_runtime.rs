@@ -0,0 +1,18 @@
+use rstest::*;
+
+#[fixture]
+async fn two() -> u32 {
+    2
+}
+
+#[rstest]
+async fn single(#[future(awt)] two: u32) {
+    assert_eq!(2, two);
+}
+
+#[rstest]
+#[case::pass(2, 4)]
+#[case::fail(3, 7)]
+async fn cases(#[future(awt)] two: u32, #[case] a: u32, #[case] expected: u32) {
+    assert_eq!(expected, a * two);
+}
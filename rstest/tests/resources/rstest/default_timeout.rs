@@ -0,0 +1,46 @@
+use rstest::*;
+use std::{rc::Rc, time::Duration};
+
+#[fixture]
+fn shared() -> u32 {
+    4
+}
+
+#[fixture]
+fn not_send() -> Rc<u32> {
+    Rc::new(4)
+}
+
+#[rstest]
+fn fast(shared: u32) {
+    assert_eq!(4, shared);
+}
+
+#[rstest]
+fn slow(shared: u32) {
+    std::thread::sleep(Duration::from_millis(1500));
+    assert_eq!(4, shared);
+}
+
+#[rstest]
+fn slow_result(shared: u32) -> Result<(), String> {
+    std::thread::sleep(Duration::from_millis(1500));
+    (shared == 4).then_some(()).ok_or("wrong".to_owned())
+}
+
+#[rstest]
+fn slow_with_rc(not_send: Rc<u32>) {
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(4, *not_send);
+}
+
+#[rstest]
+fn slow_not_send() -> Result<(), Box<dyn std::error::Error>> {
+    std::thread::sleep(Duration::from_millis(300));
+    Ok(())
+}
+
+#[rstest]
+async fn slow_async() {
+    async_std::task::sleep(Duration::from_millis(300)).await;
+}
@@ -0,0 +1,51 @@
+use rstest::*;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[fixture]
+fn pair() -> (u32, u32) {
+    (21, 2)
+}
+
+#[fixture]
+fn product(#[from(pair)] (a, b): (u32, u32)) -> u32 {
+    a * b
+}
+
+#[rstest]
+fn fixture_tuple(#[from(pair)] (a, mut b): (u32, u32), product: u32) {
+    b *= a;
+    assert_eq!(product, b);
+}
+
+#[rstest]
+#[case((1, 2), 3)]
+#[case((2, 2), 5)]
+fn case_tuple(#[case] (a, b): (u32, u32), #[case] expected: u32) {
+    assert_eq!(expected, a + b);
+}
+
+#[rstest]
+fn values_struct(
+    #[values(Point { x: 1, y: 1 }, Point { x: -2, y: 2 })] Point { x, y }: Point,
+    #[values([1, 2], [3, 4])] [first, _]: [u32; 2],
+) {
+    assert_eq!(x.abs(), y);
+    assert!(first < 3);
+}
+
+#[rstest]
+#[case::named((3, 4))]
+fn explicit_name(#[case] #[name = "sides"] (a, b): (u32, u32)) {
+    assert_eq!(25, a * a + b * b);
+}
+
+#[rstest]
+#[case(async { (2, 3) }, 6)]
+#[awt]
+async fn async_future(#[future] #[case] (a, b): (u32, u32), #[case] expected: u32) {
+    assert_eq!(expected, a * b);
+}
@@ -1,4 +1,10 @@
 struct S;
+struct D;
+impl std::fmt::Display for D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Display D")
+    }
+}
 #[rustfmt::skip] mod _skip_format {
 use rstest::*; use super::*;
 
@@ -7,15 +13,15 @@ fn fixture() -> S { S {} }
 
 #[rstest(
     ::trace)]
-fn single(fixture: S) {}
+fn single(fixture: S) { assert!(false); }
 
-#[rstest(s,
-    case(S{})
+#[rstest(s, d,
+    case(S{}, D{})
     ::trace)]
-fn cases(s: S) {}
+fn cases(s: S, d: D) { assert!(false); }
 
 #[rstest(
     s => [S{}]
     ::trace)]
-fn matrix(s: S) {}
+fn matrix(s: S) { assert!(false); }
 }
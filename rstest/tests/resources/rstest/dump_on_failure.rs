@@ -0,0 +1,42 @@
+use rstest::*;
+use std::time::Duration;
+
+#[fixture]
+fn fu32() -> u32 {
+    42
+}
+
+#[rstest]
+#[trace(on_failure)]
+fn single_pass(fu32: u32) {
+    assert_eq!(42, fu32);
+}
+
+#[rstest]
+#[trace(on_failure)]
+fn single_fail(fu32: u32) {
+    assert_eq!(24, fu32);
+}
+
+#[rstest]
+#[case::pass(42, "pass me")]
+#[case::fail(24, "fail me")]
+#[trace(on_failure)]
+fn cases(fu32: u32, #[case] expected: u32, #[case] s: &str) {
+    assert_eq!(expected, fu32);
+}
+
+#[rstest]
+#[case::ok(Ok(()))]
+#[case::err(Err("error"))]
+#[trace(on_failure)]
+fn result(#[case] r: Result<(), &'static str>) -> Result<(), &'static str> {
+    r
+}
+
+#[rstest]
+#[trace(on_failure)]
+#[timeout(Duration::from_secs(10))]
+fn timeout_fail(fu32: u32) {
+    assert_eq!(24, fu32);
+}
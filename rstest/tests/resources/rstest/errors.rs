@@ -118,3 +118,28 @@ fn error_timeout_without_duration() {}
 
 #[rstest]
 fn error_absolute_path_files(#[files("/tmp/tmp.Q81idVZYAV/*.txt")] path: std::path::PathBuf) {}
+
+#[rstest(stable_names)]
+#[case(1)]
+#[case(2)]
+#[case(1)]
+fn error_duplicate_stable_case_names(#[case] a: u32) {}
+
+#[rstest]
+#[case(a = 1, c = 2)]
+#[case(b = 2)]
+#[case(a = 1, 2)]
+fn error_named_case_args(#[case] a: u32, #[case] b: u32) {}
+
+#[rstest]
+#[xfail]
+fn error_xfail_unsupported_outcome() -> std::process::ExitCode {
+    std::process::ExitCode::SUCCESS
+}
+
+#[rstest]
+#[case(1)]
+#[case::panic(2)]
+#[should_panic]
+#[retry(2)]
+fn error_retry_with_should_panic(#[case] _a: u32) {}
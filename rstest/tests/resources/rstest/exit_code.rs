@@ -0,0 +1,19 @@
+use rstest::*;
+use std::process::ExitCode;
+
+#[rstest]
+fn single() -> ExitCode {
+    ExitCode::SUCCESS
+}
+
+#[rstest]
+fn single_fail() -> ExitCode {
+    ExitCode::FAILURE
+}
+
+#[rstest]
+#[case::pass(ExitCode::SUCCESS)]
+#[case::fail(ExitCode::FAILURE)]
+fn cases(#[case] code: ExitCode) -> ExitCode {
+    code
+}
@@ -0,0 +1,61 @@
+use rstest::*;
+use std::time::Duration;
+
+#[rstest]
+#[isolate]
+fn pass() {
+    assert!(std::env::var("RSTEST_ISOLATED_TEST").is_ok());
+}
+
+#[rstest]
+#[isolate]
+fn fail() {
+    panic!("isolated failure");
+}
+
+#[rstest]
+#[isolate]
+fn abort() {
+    std::process::abort();
+}
+
+#[rstest]
+#[isolate]
+#[timeout(Duration::from_millis(100))]
+fn timeout() {
+    loop {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[rstest]
+#[isolate]
+#[should_panic(expected = "isolated panic")]
+fn should_panic() {
+    panic!("isolated panic");
+}
+
+#[rstest]
+#[isolate]
+#[should_panic]
+fn should_panic_but_pass() {}
+
+#[rstest]
+#[isolate]
+fn error() -> Result<(), String> {
+    Err("isolated error".to_owned())
+}
+
+#[rstest]
+#[case::pass(42)]
+#[isolate]
+#[case::fail(41)]
+fn cases(#[case] value: u32) {
+    assert_eq!(42, value);
+}
+
+#[rstest]
+#[isolate]
+async fn async_pass() {
+    assert!(std::env::var("RSTEST_ISOLATED_TEST").is_ok());
+}
@@ -0,0 +1,17 @@
+use rstest::*;
+
+#[rstest]
+fn isolated() {
+    assert!(std::env::var("RSTEST_ISOLATED_TEST").is_ok());
+}
+
+#[rstest]
+fn abort() {
+    std::process::abort();
+}
+
+#[rstest]
+#[should_panic(expected = "isolated panic")]
+fn should_panic() {
+    panic!("isolated panic");
+}
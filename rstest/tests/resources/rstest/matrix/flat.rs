@@ -0,0 +1,16 @@
+use rstest::rstest;
+
+#[rstest(flat)]
+fn strlen(
+    #[values(4, 2*3-2)] expected: usize,
+    #[values(ciao => "ciao", buzz => "buzz")] input: &str,
+) {
+    assert_eq!(expected, input.len());
+}
+
+#[rstest(flat)]
+#[case::ok(4)]
+#[case::wrong(5)]
+fn with_cases(#[case] expected: usize, #[values("ciao", "buzz")] input: &str) {
+    assert_eq!(expected, input.len());
+}
@@ -0,0 +1,29 @@
+use rstest::rstest;
+
+#[rstest]
+fn named(#[values(zero => 0, one => 1, 2)] value: u32, #[values(small => 10, big => 1000)] max: u32) {
+    assert!(value < max);
+}
+
+#[rstest]
+fn checked_add(
+    #[values(ok => 1, #[should_panic] overflow => u32::MAX)] value: u32,
+) {
+    assert!(value.checked_add(1).is_some());
+}
+
+#[rstest]
+fn values_attrs(
+    #[values(1, #[should_panic] 2)] a: u32,
+    #[values(neg => -1, pos => 1)] b: i32,
+) {
+    assert_eq!(1, b.abs());
+    assert!(a < 2);
+}
+
+#[rstest(
+    value => [ok => 1, #[should_panic] zero => 0]
+)]
+fn in_rstest_attribute(value: u32) {
+    assert_eq!(1, 1 / value);
+}
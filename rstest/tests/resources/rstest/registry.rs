@@ -0,0 +1,59 @@
+use rstest::*;
+use std::time::Duration;
+
+#[rstest]
+#[case::one(1)]
+#[case(2)]
+#[timeout(Duration::from_secs(10))]
+#[tag(math)]
+fn sum(#[case] a: u32, #[values(named => 3, 4)] b: u32) {
+    assert!(a + b > 0);
+}
+
+#[rstest]
+#[should_panic]
+fn single() {
+    panic!("boom")
+}
+
+#[test]
+fn registry() {
+    let mut tests = registry::iter().collect::<Vec<_>>();
+    tests.sort_by_key(|t| t.path());
+
+    assert_eq!(
+        vec![
+            "single",
+            "sum::case_1_one::b_1_named",
+            "sum::case_1_one::b_2_4",
+            "sum::case_2::b_1_named",
+            "sum::case_2::b_2_4",
+        ],
+        tests.iter().map(|t| t.name()).collect::<Vec<_>>()
+    );
+
+    let single = tests[0];
+    assert_eq!("single", single.function());
+    assert!(single.should_panic());
+    assert!(!single.ignore());
+    assert_eq!(None, single.timeout());
+    assert_eq!(&["#[should_panic]"], single.attributes());
+    assert_eq!(13, single.line());
+    assert!(single.file().ends_with("lib.rs"));
+
+    let sum = tests[1];
+    assert_eq!("sum", sum.function());
+    assert_eq!(sum.path().split("::").next(), Some(sum.module()));
+    assert_eq!(Some("one"), sum.case());
+    assert_eq!(&[("a", "1"), ("b", "named")], sum.parameters());
+    assert_eq!(&["math"], sum.tags());
+    assert_eq!(
+        Some("Duration::from_secs(10)".to_owned()),
+        sum.timeout().map(|t| t.replace(' ', ""))
+    );
+    assert!(!sum.should_panic());
+    assert_eq!(4, sum.line());
+
+    assert_eq!(None, tests[3].case());
+    assert_eq!(&[("a", "2"), ("b", "named")], tests[3].parameters());
+}
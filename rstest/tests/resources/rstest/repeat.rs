@@ -0,0 +1,74 @@
+use rstest::*;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Barrier, OnceLock,
+};
+use std::time::Duration;
+
+static RESOLVED: AtomicU32 = AtomicU32::new(0);
+static ONCE: AtomicU32 = AtomicU32::new(0);
+
+#[fixture]
+fn iteration() -> u32 {
+    RESOLVED.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[fixture]
+#[once]
+fn once() -> u32 {
+    ONCE.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[rstest]
+#[repeat(5)]
+fn fresh_fixtures(iteration: u32, once: &u32) {
+    static ITERATIONS: AtomicU32 = AtomicU32::new(0);
+    assert_eq!(1, *once);
+    assert_eq!(ITERATIONS.fetch_add(1, Ordering::SeqCst) + 1, iteration);
+}
+
+#[rstest]
+#[repeat(100)]
+fn fail_at_iteration() -> Result<(), String> {
+    static ITERATIONS: AtomicU32 = AtomicU32::new(0);
+    match ITERATIONS.fetch_add(1, Ordering::SeqCst) + 1 {
+        7 => Err("boom".to_owned()),
+        iteration if iteration > 7 => panic!("run after the failure"),
+        _ => Ok(()),
+    }
+}
+
+static SINGLE: AtomicU32 = AtomicU32::new(0);
+static REPEATED: AtomicU32 = AtomicU32::new(0);
+
+#[rstest]
+#[case::single(&SINGLE, 1)]
+#[repeat(3)]
+#[case::repeated(&REPEATED, 3)]
+fn cases(#[case] counter: &AtomicU32, #[case] max: u32) {
+    assert!(counter.fetch_add(1, Ordering::SeqCst) < max);
+}
+
+#[rstest]
+#[repeat(100, threads = 4)]
+#[timeout(Duration::from_secs(10))]
+fn concurrent() {
+    // Every iteration waits for the other threads: it would hang without 4 threads
+    static BARRIER: OnceLock<Barrier> = OnceLock::new();
+    BARRIER.get_or_init(|| Barrier::new(4)).wait();
+}
+
+#[rstest]
+#[repeat(100, threads = 4)]
+fn concurrent_fail() {
+    static ITERATIONS: AtomicU32 = AtomicU32::new(0);
+    assert!(ITERATIONS.fetch_add(1, Ordering::SeqCst) < 50, "too many iterations");
+}
+
+#[rstest]
+#[repeat(3)]
+async fn async_repeat() {
+    static ITERATIONS: AtomicU32 = AtomicU32::new(0);
+    async_std::task::yield_now().await;
+    assert!(ITERATIONS.fetch_add(1, Ordering::SeqCst) < 2);
+}
@@ -0,0 +1,54 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+#[case::pass(2, 2)]
+#[case::fail(2, 3)]
+#[case(4, 4)]
+fn cases(#[case] a: u32, #[case] expected: u32) {
+    assert_eq!(expected, a);
+}
+
+#[rstest]
+fn values(#[values(ok => Ok(()), Err("bad"))] r: Result<(), &'static str>) -> Result<(), &'static str> {
+    r
+}
+
+#[rstest]
+fn files(#[files("files/*.txt")] path: PathBuf) {
+    assert!(path.exists());
+}
+
+#[rstest]
+#[skip_if(true, reason = "not today")]
+fn skipped() {
+    panic!("should be skipped");
+}
+
+#[rstest]
+#[should_panic]
+fn expected_panic() {
+    panic!("expected");
+}
+
+#[rstest]
+fn exit_code() -> std::process::ExitCode {
+    std::process::ExitCode::SUCCESS
+}
+
+#[rstest]
+#[isolate]
+fn isolated_pass() {}
+
+#[rstest]
+#[isolate]
+fn isolated_fail() {
+    panic!("isolated failure");
+}
+
+#[rstest]
+#[isolate]
+#[skip_if(true, reason = "isolated skip")]
+fn isolated_skipped() {
+    panic!("should be skipped");
+}
@@ -0,0 +1,67 @@
+use rstest::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+fn ms(ms: u32) -> Duration {
+    Duration::from_millis(ms.into())
+}
+
+static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+static RESOLVED: AtomicU32 = AtomicU32::new(0);
+static ONCE: AtomicU32 = AtomicU32::new(0);
+
+#[fixture]
+fn attempt() -> u32 {
+    RESOLVED.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[fixture]
+#[once]
+fn once() -> u32 {
+    ONCE.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[rstest]
+#[retry(2)]
+fn flaky(attempt: u32, once: &u32) {
+    assert_eq!(1, *once);
+    assert_eq!(3, attempt, "attempt {attempt} failed");
+}
+
+#[rstest]
+#[retry(2)]
+fn always_fail() {
+    panic!("attempt {} failed", ATTEMPTS.fetch_add(1, Ordering::SeqCst) + 1);
+}
+
+static RETRIED: AtomicU32 = AtomicU32::new(0);
+static NOT_RETRIED: AtomicU32 = AtomicU32::new(0);
+
+#[rstest]
+#[retry(1)]
+#[case::retried(&RETRIED)]
+#[case::not_retried(&NOT_RETRIED)]
+fn cases(#[case] attempts: &AtomicU32) -> Result<(), String> {
+    match attempts.fetch_add(1, Ordering::SeqCst) {
+        0 => Err("first attempt".to_owned()),
+        _ => Ok(()),
+    }
+}
+
+#[rstest]
+#[retry(1)]
+#[timeout(ms(100))]
+fn timeout() {
+    static TIMEOUT_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+    if TIMEOUT_ATTEMPTS.fetch_add(1, Ordering::SeqCst) == 0 {
+        std::thread::sleep(ms(1000));
+    }
+}
+
+#[rstest]
+#[retry(1)]
+async fn async_flaky() {
+    static ASYNC_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+    async_std::task::yield_now().await;
+    assert_eq!(1, ASYNC_ATTEMPTS.fetch_add(1, Ordering::SeqCst));
+}
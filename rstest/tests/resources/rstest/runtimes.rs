@@ -0,0 +1,37 @@
+use rstest::*;
+use std::time::Duration;
+
+#[fixture]
+async fn base() -> u32 {
+    42
+}
+
+#[rstest]
+#[runtimes(tokio, async_std, smol)]
+async fn single(#[future(awt)] base: u32) {
+    assert_eq!(42, base);
+}
+
+#[rstest]
+#[case(42)]
+#[case(41)]
+#[runtimes(tokio, smol)]
+async fn cases(#[future(awt)] base: u32, #[case] expected: u32) {
+    assert_eq!(expected, base);
+}
+
+#[rstest]
+#[runtimes(tokio_multi_thread)]
+async fn multi_thread() {
+    assert_eq!(
+        tokio::runtime::RuntimeFlavor::MultiThread,
+        tokio::runtime::Handle::current().runtime_flavor()
+    );
+}
+
+#[rstest]
+#[runtimes(tokio, async_std, smol)]
+#[timeout(Duration::from_millis(10))]
+async fn timeout() {
+    std::future::pending::<()>().await
+}
@@ -0,0 +1,77 @@
+use rstest::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+static EXCLUSIVE: AtomicU32 = AtomicU32::new(0);
+static SHARED: AtomicU32 = AtomicU32::new(0);
+static DEFAULT: AtomicU32 = AtomicU32::new(0);
+
+/// Count the running tests till the fixture teardown.
+struct Running(&'static AtomicU32);
+
+impl Running {
+    fn start(counter: &'static AtomicU32) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        std::thread::sleep(Duration::from_millis(10));
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[fixture]
+fn exclusive() -> Running {
+    Running::start(&EXCLUSIVE)
+}
+
+#[fixture]
+fn shared() -> Running {
+    Running::start(&SHARED)
+}
+
+fn check_alone() {
+    assert_eq!(1, EXCLUSIVE.load(Ordering::SeqCst));
+    assert_eq!(0, SHARED.load(Ordering::SeqCst));
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+#[rstest::serial(resource)]
+fn serial_cases(exclusive: Running, #[case] _n: u32) {
+    check_alone();
+    std::thread::sleep(Duration::from_millis(20));
+    check_alone();
+}
+
+#[rstest]
+#[rstest::serial(resource)]
+async fn serial_async(exclusive: Running) {
+    check_alone();
+    async_std::task::sleep(Duration::from_millis(20)).await;
+    check_alone();
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+#[rstest::parallel(resource)]
+fn parallel_cases(shared: Running, #[case] _n: u32) {
+    assert_eq!(0, EXCLUSIVE.load(Ordering::SeqCst));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(0, EXCLUSIVE.load(Ordering::SeqCst));
+}
+
+#[rstest]
+#[rstest::serial]
+fn default_key(#[values(1, 2, 3)] _n: u32) {
+    assert_eq!(0, DEFAULT.fetch_add(1, Ordering::SeqCst));
+    std::thread::sleep(Duration::from_millis(20));
+    DEFAULT.fetch_sub(1, Ordering::SeqCst);
+}
@@ -0,0 +1,71 @@
+use rstest::*;
+
+#[fixture]
+fn unreachable() -> u32 {
+    panic!("should not be resolved")
+}
+
+fn always() -> bool {
+    true
+}
+
+#[rstest]
+#[skip_if(always(), reason = "no database")]
+fn skip(unreachable: u32) {
+    assert_eq!(42, unreachable);
+}
+
+#[rstest]
+#[skip_if(!always())]
+fn not_skip() {}
+
+#[rstest]
+#[skip_if(always())]
+fn skip_result(unreachable: u32) -> Result<(), String> {
+    Err(format!("{unreachable}"))
+}
+
+#[rstest]
+#[case(1)]
+#[skip_if(always(), reason = "unsupported")]
+#[case(2)]
+fn cases(#[case] value: u32) {
+    assert_eq!(1, value);
+}
+
+#[rstest]
+fn values(
+    #[values(1, 2, 3)]
+    #[skip_if(value > 1, reason = "too big")]
+    value: u32,
+    unreachable: u32,
+) {
+    assert_eq!(value, unreachable);
+}
+
+#[rstest]
+#[skip_if(always())]
+async fn skip_async(unreachable: u32) {
+    assert_eq!(42, unreachable);
+}
+
+#[rstest]
+#[skip_if(always())]
+fn skip_exit_code(unreachable: u32) -> std::process::ExitCode {
+    std::process::ExitCode::from(unreachable as u8)
+}
+
+struct NoPassingValue;
+
+impl std::process::Termination for NoPassingValue {
+    fn report(self) -> std::process::ExitCode {
+        std::process::ExitCode::SUCCESS
+    }
+}
+
+#[rstest]
+#[skip_if(always())]
+fn skip_without_passing_value(unreachable: u32) -> NoPassingValue {
+    assert_eq!(42, unreachable);
+    NoPassingValue
+}
@@ -0,0 +1,32 @@
+use rstest::*;
+
+#[rstest]
+#[tag(db)]
+fn db() {}
+
+#[rstest]
+#[tag(db, slow)]
+fn db_slow() {}
+
+#[rstest]
+fn untagged() {}
+
+#[rstest]
+#[case::fast(1)]
+#[tag(slow)]
+#[case::slow(2)]
+#[tag(net)]
+fn cases(#[case] n: u32) {
+    assert!(n > 0);
+}
+
+#[rstest]
+fn values(#[values(1, #[tag(slow)] 2)] n: u32) {
+    assert!(n > 0);
+}
+
+#[rstest]
+#[tag(slow)]
+fn slow_exit_code() -> std::process::ExitCode {
+    std::process::ExitCode::FAILURE
+}
@@ -0,0 +1,36 @@
+use rstest::*;
+use std::time::Duration;
+
+fn ms(ms: u32) -> Duration {
+    Duration::from_millis(ms.into())
+}
+
+#[fixture]
+fn slow() -> u32 {
+    std::thread::sleep(ms(200));
+    42
+}
+
+#[fixture]
+async fn slow_async() -> u32 {
+    async_std::task::sleep(ms(200)).await;
+    42
+}
+
+#[rstest]
+#[timeout(ms(50))]
+fn fixture_phase(slow: u32) {
+    assert_eq!(42, slow);
+}
+
+#[rstest]
+#[timeout(ms(50))]
+fn body_phase() {
+    std::thread::sleep(ms(200));
+}
+
+#[rstest]
+#[timeout(ms(50))]
+async fn await_phase(#[future(awt)] slow_async: u32) {
+    assert_eq!(42, slow_async);
+}
@@ -0,0 +1,54 @@
+use rstest::*;
+use std::{rc::Rc, time::Duration};
+
+fn ms(ms: u32) -> Duration {
+    Duration::from_millis(ms.into())
+}
+
+fn delayed_sum(a: u32, b: u32, delay: Duration) -> u32 {
+    std::thread::sleep(delay);
+    a + b
+}
+
+#[fixture]
+fn shared() -> Rc<u32> {
+    Rc::new(4)
+}
+
+#[rstest]
+#[timeout(ms(80), watchdog)]
+fn single_pass(shared: Rc<u32>) {
+    assert_eq!(*shared, delayed_sum(2, 2, ms(10)));
+}
+
+#[rstest]
+#[timeout(ms(100), watchdog)]
+fn single_fail_value(shared: Rc<u32>) {
+    assert_eq!(*shared + 1, delayed_sum(2, 2, ms(1)));
+}
+
+#[rstest]
+#[timeout(ms(10), watchdog)]
+fn single_fail_timeout(shared: Rc<u32>) {
+    assert_eq!(*shared, delayed_sum(2, 2, ms(80)));
+}
+
+#[rstest]
+#[case::pass(ms(1))]
+#[case::fail_timeout(ms(80))]
+#[timeout(ms(40), watchdog)]
+fn group(shared: Rc<u32>, #[case] delay: Duration) -> Result<(), Rc<u32>> {
+    match delayed_sum(2, 2, delay) == *shared {
+        true => Ok(()),
+        false => Err(shared),
+    }
+}
+
+#[rstest]
+#[timeout(ms(10), watchdog)]
+fn unwind_at_checkpoint() {
+    loop {
+        std::thread::sleep(ms(1));
+        rstest::timeout::checkpoint();
+    }
+}
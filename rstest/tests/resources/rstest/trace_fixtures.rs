@@ -0,0 +1,34 @@
+use rstest::*;
+
+#[fixture]
+fn base() -> u32 {
+    21
+}
+
+#[fixture]
+fn double(base: u32, #[default(2)] factor: u32) -> u32 {
+    base * factor
+}
+
+#[fixture]
+fn name(#[default("rstest")] n: &str) -> String {
+    n.to_string()
+}
+
+#[rstest]
+#[trace(fixtures)]
+fn nested(double: u32, name: String) {
+    assert!(false);
+}
+
+#[rstest]
+#[trace(fixtures)]
+fn with_args(#[with(3)] double: u32, #[from(name)] other: String) {
+    assert!(false);
+}
+
+#[rstest]
+#[trace]
+fn not_traced(double: u32) {
+    assert!(false);
+}
@@ -0,0 +1,43 @@
+use rstest::*;
+use std::time::Duration;
+
+fn ms(ms: u32) -> Duration {
+    Duration::from_millis(ms.into())
+}
+
+#[rstest]
+#[warn_slow(ms(1000))]
+fn fast() {}
+
+#[rstest]
+#[warn_slow(ms(10))]
+fn slow() {
+    std::thread::sleep(ms(50));
+}
+
+#[rstest]
+#[warn_slow(ms(10))]
+fn slow_and_failed() {
+    std::thread::sleep(ms(50));
+    panic!("failed");
+}
+
+#[rstest]
+#[warn_slow(ms(10))]
+fn slow_and_error() -> Result<(), String> {
+    std::thread::sleep(ms(50));
+    Err("failed".to_owned())
+}
+
+#[rstest]
+#[case::fast(ms(1))]
+#[warn_slow(ms(10))]
+#[case::slow(ms(50))]
+fn cases(#[case] delay: Duration) {
+    std::thread::sleep(delay);
+}
+
+#[rstest]
+fn default_threshold() {
+    std::thread::sleep(ms(150));
+}
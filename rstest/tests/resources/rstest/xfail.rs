@@ -0,0 +1,56 @@
+use rstest::*;
+use std::time::Duration;
+
+#[rstest]
+#[xfail(reason = "known bug")]
+fn panic() {
+    panic!("boom");
+}
+
+#[rstest]
+#[xfail]
+fn error() -> Result<(), String> {
+    Err("some error".to_owned())
+}
+
+#[rstest]
+#[xfail(reason = "already fixed")]
+fn xpass() {}
+
+#[rstest]
+#[xfail(reason = "sometimes fixed", strict = false)]
+fn xpass_not_strict() {}
+
+#[rstest]
+#[case(1)]
+#[xfail(reason = "2 is not 1")]
+#[case(2)]
+#[xfail(reason = "already fixed")]
+#[case(1)]
+fn cases(#[case] value: u32) {
+    assert_eq!(1, value);
+}
+
+#[rstest]
+#[xfail(reason = "too slow")]
+#[timeout(Duration::from_millis(10))]
+fn timeout() {
+    std::thread::sleep(Duration::from_millis(100));
+}
+
+#[rstest]
+#[xfail]
+async fn async_panic() {
+    panic!("boom");
+}
+
+#[rstest]
+#[xfail]
+#[timeout(Duration::from_millis(10))]
+async fn async_timeout() {
+    async_std::task::sleep(Duration::from_millis(100)).await;
+}
+
+#[rstest]
+#[xfail]
+async fn async_xpass() {}
@@ -21,6 +21,120 @@ fn run_test(res: impl AsRef<Path>) -> (std::process::Output, String) {
     )
 }
 
+fn features_prj(res: impl AsRef<Path>, features: &[&str]) -> Project {
+    let prj = crate::base_prj().set_code_file(resources(res));
+    prj.add_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features = {features:?}}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+    prj
+}
+
+#[test]
+fn report() {
+    let mut prj = features_prj("report.rs", &["report"]);
+    let files_path = prj.path().join("files");
+    std::fs::create_dir(&files_path).unwrap();
+    File::create(files_path.join("data.txt")).unwrap();
+    let json = prj.path().join("report.jsonl");
+    let junit = prj.path().join("junit");
+    std::fs::create_dir(&junit).unwrap();
+    prj.set_env("RSTEST_REPORT_JSON", json.display());
+    prj.set_env("RSTEST_REPORT_JUNIT", junit.display());
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("cases::case_1_pass")
+        .fail("cases::case_2_fail")
+        .ok("cases::case_3")
+        .ok("values::r_1_ok")
+        .fail("values::r_2_Err___bad___")
+        .ok("files::path_1_files_data_txt")
+        .ok("skipped")
+        .ok("expected_panic")
+        .ok("exit_code")
+        .ok("isolated_pass")
+        .fail("isolated_fail")
+        .ok("isolated_skipped")
+        .assert(output);
+
+    let json = std::fs::read_to_string(json).unwrap();
+    let record = |test: &str| {
+        json.lines()
+            .find(|l| l.contains(&format!(r#""test":"{test}""#)))
+            .unwrap_or_else(|| panic!("Cannot find {test} in {json}"))
+    };
+    assert_eq!(12, json.lines().count());
+    assert_in!(
+        record("cases::case_1_pass"),
+        r#""function":"cases","case":"pass","parameters":{"a":"2","expected":"2"},"tags":[],"status":"passed""#
+    );
+    assert_in!(record("cases::case_2_fail"), r#""status":"failed""#);
+    assert_in!(record("cases::case_2_fail"), r#"left: 3\n right: 2"#);
+    assert_in!(record("cases::case_3"), r#""case":null"#);
+    assert_in!(
+        record("values::r_2_Err___bad___"),
+        r#""parameters":{"r":"Err(\"bad\")"},"tags":[],"status":"failed""#
+    );
+    assert_in!(
+        record("values::r_2_Err___bad___"),
+        r#""message":"Error: \"bad\"""#
+    );
+    assert_in!(
+        record("files::path_1_files_data_txt"),
+        r#""parameters":{"path":"files/data.txt"}"#
+    );
+    assert_in!(record("skipped"), r#""status":"skipped","duration":"#);
+    assert_in!(record("skipped"), r#""message":"not today""#);
+    assert_in!(record("expected_panic"), r#""status":"passed""#);
+    assert_in!(record("exit_code"), r#""status":"passed""#);
+    assert_in!(record("isolated_pass"), r#""status":"passed""#);
+    assert_in!(record("isolated_fail"), r#""status":"failed""#);
+    assert_in!(record("isolated_fail"), "isolated failure");
+    assert_in!(record("isolated_skipped"), r#""status":"skipped""#);
+    assert_in!(record("isolated_skipped"), r#""message":"isolated skip""#);
+
+    let junit = std::fs::read_dir(junit)
+        .unwrap()
+        .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(1, junit.len());
+    assert_in!(junit[0], r#"tests="12" failures="3" skipped="2""#);
+    assert_in!(junit[0], r#"<testcase name="case_2_fail" classname="#);
+    assert_in!(
+        junit[0],
+        r#"<property name="parameter.expected" value="3"/>"#
+    );
+    assert_in!(junit[0], r#"<skipped message="not today"/>"#);
+}
+
+#[test]
+fn registry() {
+    let prj = crate::base_prj().set_code_file(resources("registry.rs"));
+    prj.add_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features = ["registry"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("registry")
+        .ok("single")
+        .ok("sum::case_1_one::b_1_named")
+        .ok("sum::case_1_one::b_2_4")
+        .ok("sum::case_2::b_1_named")
+        .ok("sum::case_2::b_2_4")
+        .assert(output);
+}
+
 #[test]
 fn files() {
     let prj = prj("files.rs");
@@ -77,6 +191,221 @@ fn files() {
         .assert(output);
 }
 
+#[test]
+fn destructuring_patterns() {
+    let prj = prj("destruct.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("fixture_tuple")
+        .ok("case_tuple::case_1")
+        .fail("case_tuple::case_2")
+        .ok("values_struct::arg_1_1_Point_x_1_y_1_::arg_2_1__1_2_")
+        .fail("values_struct::arg_1_1_Point_x_1_y_1_::arg_2_2__3_4_")
+        .ok("values_struct::arg_1_2_Point_x__2_y_2_::arg_2_1__1_2_")
+        .fail("values_struct::arg_1_2_Point_x__2_y_2_::arg_2_2__3_4_")
+        .ok("explicit_name::case_1_named")
+        .ok("async_future::case_1")
+        .assert(output);
+}
+
+#[test]
+fn xfail() {
+    let prj = prj("xfail.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("panic")
+        .ok("error")
+        .fail("xpass")
+        .ok("xpass_not_strict")
+        .ok("cases::case_1")
+        .ok("cases::case_2")
+        .fail("cases::case_3")
+        .ok("timeout")
+        .ok("async_panic")
+        .ok("async_timeout")
+        .fail("async_xpass")
+        .assert(output.clone());
+
+    assert_in!(
+        output.stdout.str(),
+        "XPASS: already fixed: test passed unexpectedly"
+    );
+}
+
+#[test]
+fn isolate() {
+    let prj = prj("isolate.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("pass")
+        .fail("fail")
+        .fail("abort")
+        .fail("timeout")
+        .ok("should_panic")
+        .fail("should_panic_but_pass")
+        .fail("error")
+        .ok("cases::case_1_pass")
+        .fail("cases::case_2_fail")
+        .ok("async_pass")
+        .assert(output.clone());
+
+    let stdout = output.stdout.str();
+    assert_in!(stdout, "isolated failure");
+    assert_in!(stdout, "Isolated test process killed by signal");
+    assert_in!(
+        stdout,
+        "Timeout 100ms expired: isolated test process killed"
+    );
+    assert_in!(stdout, "isolated error");
+    assert_in!(stdout, "Expected panic but isolated test process passed");
+}
+
+#[test]
+fn isolate_by_env() {
+    let mut prj = crate::base_prj().set_code_file(resources("isolate_by_env.rs"));
+    prj.add_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features = ["isolate"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+    prj.set_env("RSTEST_ISOLATE", "1");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("isolated")
+        .fail("abort")
+        .ok("should_panic")
+        .assert(output.clone());
+
+    assert_in!(
+        output.stdout.str(),
+        "Isolated test process killed by signal"
+    );
+}
+
+#[test]
+fn runtimes() {
+    let prj = prj("runtimes.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    prj.add_dependency(
+        "tokio",
+        r#"{version="1", features=["rt", "rt-multi-thread", "macros"]}"#,
+    );
+    prj.add_dependency("smol", r#""*""#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("single::tokio")
+        .ok("single::async_std")
+        .ok("single::smol")
+        .ok("cases::case_1::tokio")
+        .ok("cases::case_1::smol")
+        .fail("cases::case_2::tokio")
+        .fail("cases::case_2::smol")
+        .ok("multi_thread::tokio_multi_thread")
+        .fail("timeout::tokio")
+        .fail("timeout::async_std")
+        .fail("timeout::smol")
+        .assert(output);
+}
+
+/// A project that uses the `rstest` crate with the given `feature` to select the default
+/// runtime of the async tests.
+fn default_runtime_prj(feature: &str) -> Project {
+    let prj = crate::base_prj().set_code_file(resources("default_runtime.rs"));
+    prj.add_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features = ["{feature}"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+    prj
+}
+
+#[test]
+fn default_runtime_tokio() {
+    let prj = default_runtime_prj("tokio");
+    prj.add_dependency("tokio", r#"{version="1", features=["rt", "macros"]}"#);
+    prj.append_code(
+        r#"
+        #[rstest]
+        async fn tokio_runtime() {
+            tokio::runtime::Handle::current();
+        }
+        "#,
+    );
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("single")
+        .ok("cases::case_1_pass")
+        .fail("cases::case_2_fail")
+        .ok("tokio_runtime")
+        .assert(output);
+}
+
+#[test]
+fn default_runtime_smol() {
+    let prj = default_runtime_prj("smol");
+    prj.add_dependency("smol", r#""*""#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("single")
+        .ok("cases::case_1_pass")
+        .fail("cases::case_2_fail")
+        .assert(output);
+}
+
+#[test]
+fn skip_if() {
+    let prj = prj("skip_if.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("skip")
+        .ok("not_skip")
+        .ok("skip_result")
+        .ok("cases::case_1")
+        .ok("cases::case_2")
+        .fail("values::value_1_1")
+        .ok("values::value_2_2")
+        .ok("values::value_3_3")
+        .ok("skip_async")
+        .ok("skip_exit_code")
+        .fail("skip_without_passing_value")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    assert_in!(
+        output.stdout.str(),
+        "NoPassingValue` doesn't implement `rstest::outcome::Passed`"
+    );
+    assert_in!(stderr, "SKIPPED skip: no database");
+    assert_in!(stderr, "SKIPPED skip_result: always()");
+    assert_in!(stderr, "SKIPPED cases::case_2: unsupported");
+    assert_in!(stderr, "SKIPPED values::value_2_2: too big");
+    assert_not_in!(stderr, "SKIPPED not_skip");
+}
+
 #[test]
 fn mutable_input() {
     let (output, _) = run_test("mut.rs");
@@ -284,28 +613,25 @@ mod dump_input_values {
     #[rstest]
     #[case::compact_syntax("dump_not_debug_compact.rs")]
     #[case::attr_syntax("dump_not_debug.rs")]
-    fn should_not_compile_if_not_implement_debug(#[case] source: &str) {
-        let (output, name) = run_test(source);
-
-        assert_all_in!(
-            output.stderr.str(),
-            format!("--> {}/src/lib.rs:10:11", name),
-            "fn single(fixture: S) {}",
-            "^^^^^^^ `S` cannot be formatted using `{:?}`"
-        );
+    fn should_fallback_if_not_implement_debug(#[case] source: &str) {
+        let (output, _) = run_test(source);
+        let out = output.stdout.str().to_string();
 
-        assert_in!(
-            output.stderr.str(),
-            format!("--> {}/src/lib.rs:15:10", name),
-            "fn cases(s: S) {}",
-            "^ `S` cannot be formatted using `{:?}`"
-        );
+        TestResults::new()
+            .fail("_skip_format::single")
+            .fail("_skip_format::cases::case_1")
+            .fail("_skip_format::matrix::s_1_S__")
+            .assert(output);
 
-        assert_in!(
-            output.stderr.str(),
-            format!("--> {}/src/lib.rs:20:11", name),
-            "fn matrix(s: S) {}",
-            "^ `S` cannot be formatted using `{:?}`"
+        assert_in!(out, "fixture = ");
+        assert_in!(out, "::S (unprintable)");
+        assert_in!(out, "d = Display D");
+        assert_eq!(
+            3,
+            out.lines()
+                .filter(|l| l.starts_with("s = ") || l.starts_with("fixture = "))
+                .filter(|l| l.ends_with("::S (unprintable)"))
+                .count()
         );
     }
 
@@ -350,6 +676,77 @@ mod dump_input_values {
             lines.join("\n")
         );
     }
+
+    #[test]
+    fn pretty_print_and_truncate_by_env() {
+        let mut prj = prj("dump_debug.rs");
+        prj.set_env("RSTEST_TRACE_PRETTY", "1");
+        prj.set_env("RSTEST_TRACE_MAX_LEN", "20");
+
+        let output = prj.run_tests().unwrap();
+        let out = output.stdout.str().to_string();
+
+        assert_in!(out, r#"fstring = "A String""#);
+        assert_in!(out, "ftuple = (\n    A,\n");
+        assert_in!(out, "... (15 more chars)");
+    }
+
+    #[test]
+    fn trace_fixtures() {
+        let prj = prj("trace_fixtures.rs");
+        let output = prj.run_tests().unwrap();
+        let out = output.stdout.str().to_string();
+        let traced = |test: &str| {
+            out.lines()
+                .skip_while(|l| !l.contains(&format!("---- {test} stdout ----")))
+                .skip(1)
+                .take_while(|l| !l.contains("TEST ARGUMENTS"))
+                .map(|l| l.replace(&format!("{}::", prj.get_name()), ""))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![
+                "resolve double::default",
+                "    resolve base::default",
+                "    base() = 21",
+                "double(base = 21, factor = 2) = 42",
+                "resolve name::default",
+                r#"name(n = "rstest") = "rstest""#,
+            ],
+            traced("nested")
+        );
+        assert_eq!(
+            vec![
+                "resolve double::partial_1",
+                "double(base = 3, factor = 2) = 6",
+                "resolve name::default",
+                r#"name(n = "rstest") = "rstest""#,
+            ],
+            traced("with_args")
+        );
+        assert!(traced("not_traced").is_empty());
+    }
+
+    #[test]
+    fn only_on_failure() {
+        let prj = prj("dump_on_failure.rs").with_nocapture();
+        let output = prj.run_tests().unwrap();
+        let out = output.stdout.str().to_string();
+
+        // Without capture the traced arguments are mixed with the test harness output
+        assert_in!(out, "3 passed; 4 failed");
+        assert_in!(out, "TEST ARGUMENTS single_fail ");
+        assert_in!(out, "TEST ARGUMENTS cases::case_2_fail ");
+        assert_in!(out, r#"s = "fail me""#);
+        assert_in!(out, "TEST ARGUMENTS result::case_2_err ");
+        assert_in!(out, r#"r = Err("error")"#);
+        assert_in!(out, "TEST ARGUMENTS timeout_fail ");
+        assert_not_in!(out, "TEST ARGUMENTS single_pass ");
+        assert_not_in!(out, r#"s = "pass me""#);
+        assert_not_in!(out, "TEST ARGUMENTS result::case_1_ok ");
+        assert_eq!(3, out.lines().filter(|l| l.contains("fu32 = 42")).count());
+    }
 }
 
 mod single {
@@ -491,6 +888,39 @@ mod cases {
             .assert(output);
     }
 
+    #[test]
+    fn named_arguments() {
+        let (output, _) = run_test(res("named_args.rs"));
+
+        TestResults::new()
+            .ok("square::case_1")
+            .ok("square::case_2")
+            .ok("square::case_3")
+            .fail("square::case_4_fail")
+            .ok("strlen::case_1")
+            .ok("strlen::case_2")
+            .ok("matrix::case_1::c_1_3")
+            .ok("matrix::case_1::c_2_4")
+            .fail("matrix::case_2::c_1_3")
+            .fail("matrix::case_2::c_2_4")
+            .assert(output);
+    }
+
+    #[test]
+    fn stable_names() {
+        let (output, _) = run_test(res("stable_names.rs"));
+
+        TestResults::new()
+            .ok("square::case_2_4")
+            .ok("square::case_three")
+            .ok("square::case__1_1")
+            .fail("square::case_4_15")
+            .ok("strlen::case___ciao___4")
+            .ok("strlen::case_String__from___hello____5")
+            .ok("long::case___averylongstringthatisusedtocheckthattoolongnam_60194417")
+            .assert(output);
+    }
+
     #[test]
     fn should_apply_partial_fixture() {
         let (output, _) = run_test(res("partial.rs"));
@@ -920,6 +1350,44 @@ mod matrix {
             .ok("second::expected_2_2_3_2::input_2___buzz__")
             .assert(output);
     }
+
+    #[test]
+    fn use_values_names_and_attributes() {
+        let (output, _) = run_test(res("named_values.rs"));
+
+        TestResults::new()
+            .ok("named::value_1_zero::max_1_small")
+            .ok("named::value_1_zero::max_2_big")
+            .ok("named::value_2_one::max_1_small")
+            .ok("named::value_2_one::max_2_big")
+            .ok("named::value_3_2::max_1_small")
+            .ok("named::value_3_2::max_2_big")
+            .ok("checked_add::value_1_ok")
+            .ok("checked_add::value_2_overflow")
+            .ok("values_attrs::a_1_1::b_1_neg")
+            .ok("values_attrs::a_1_1::b_2_pos")
+            .ok("values_attrs::a_2_2::b_1_neg")
+            .ok("values_attrs::a_2_2::b_2_pos")
+            .ok("in_rstest_attribute::value_1_ok")
+            .ok("in_rstest_attribute::value_2_zero")
+            .assert(output);
+    }
+
+    #[test]
+    fn flat() {
+        let (output, _) = run_test(res("flat.rs"));
+
+        TestResults::new()
+            .ok("strlen::expected_1_4__input_1_ciao")
+            .ok("strlen::expected_1_4__input_2_buzz")
+            .ok("strlen::expected_2_2_3_2__input_1_ciao")
+            .ok("strlen::expected_2_2_3_2__input_2_buzz")
+            .ok("with_cases::case_1_ok__input_1___ciao__")
+            .ok("with_cases::case_1_ok__input_2___buzz__")
+            .fail("with_cases::case_2_wrong__input_1___ciao__")
+            .fail("with_cases::case_2_wrong__input_2___buzz__")
+            .assert(output);
+    }
 }
 
 #[test]
@@ -1046,6 +1514,434 @@ fn timeout() {
         .assert(output);
 }
 
+#[test]
+fn default_timeout_should_be_resolved_when_test_runs() {
+    let mut prj = prj("default_timeout.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    prj.set_default_timeout("100ms");
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("fast")
+        .fail("slow")
+        .fail("slow_result")
+        .fail("slow_with_rc")
+        .ok("slow_not_send")
+        .fail("slow_async")
+        .assert(output.clone());
+
+    assert_in!(output.stdout.str(), "Timeout 100ms expired");
+    assert_not_in!(output.stderr.str(), "slow_result: RSTEST_TIMEOUT");
+    assert_in!(
+        output.stderr.str(),
+        "slow_not_send: RSTEST_TIMEOUT 100ms ignored"
+    );
+
+    prj.set_default_timeout("3s");
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("fast")
+        .ok("slow")
+        .ok("slow_result")
+        .ok("slow_with_rc")
+        .ok("slow_not_send")
+        .ok("slow_async")
+        .assert(output);
+}
+
+#[test]
+fn timeout_should_report_the_phase() {
+    let prj = prj("timeout_phases.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .fail("fixture_phase")
+        .fail("body_phase")
+        .fail("await_phase")
+        .assert(output.clone());
+
+    let stdout = output.stdout.str();
+    assert_in!(
+        stdout,
+        "Timeout 50ms expired while resolving fixture `slow`"
+    );
+    assert_in!(stdout, "Timeout 50ms expired while running the test body");
+    assert_in!(
+        stdout,
+        "Timeout 50ms expired while awaiting `#[future(awt)]` arguments"
+    );
+}
+
+#[test]
+fn warn_slow() {
+    let mut prj = crate::base_prj().set_code_file(resources("warn_slow.rs"));
+    prj.add_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features = ["warn-slow"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+    let report = prj.path().join("slow_report.tsv");
+    prj.set_env("RSTEST_WARN_SLOW", "100ms");
+    prj.set_env("RSTEST_SLOW_REPORT", report.display());
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("fast")
+        .ok("slow")
+        .fail("slow_and_failed")
+        .fail("slow_and_error")
+        .ok("cases::case_1_fast")
+        .ok("cases::case_2_slow")
+        .ok("default_threshold")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    assert_in!(stderr, "*** SLOW TEST slow: took");
+    assert_in!(stderr, "(threshold 10ms) ***");
+    assert_in!(stderr, "*** SLOW TEST cases::case_2_slow: took");
+    assert_in!(stderr, "(threshold 100ms) ***");
+    assert_not_in!(stderr, "SLOW TEST fast");
+    assert_not_in!(stderr, "SLOW TEST slow_and_failed");
+    assert_not_in!(stderr, "SLOW TEST slow_and_error");
+    assert_not_in!(stderr, "SLOW TEST cases::case_1_fast");
+
+    let report = std::fs::read_to_string(report).unwrap();
+    let mut names = report
+        .lines()
+        .map(|line| line.split('\t').next().unwrap())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(
+        vec!["cases::case_2_slow", "default_threshold", "slow"],
+        names
+    );
+}
+
+#[test]
+fn serial() {
+    let mut prj = prj("serial.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    // The tests should run concurrently to check the locks
+    prj.set_env("RUST_TEST_THREADS", "8");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("serial_cases::case_1")
+        .ok("serial_cases::case_2")
+        .ok("serial_cases::case_3")
+        .ok("serial_async")
+        .ok("parallel_cases::case_1")
+        .ok("parallel_cases::case_2")
+        .ok("parallel_cases::case_3")
+        .ok("default_key::_n_1_1")
+        .ok("default_key::_n_2_2")
+        .ok("default_key::_n_3_3")
+        .assert(output);
+}
+
+#[rstest]
+#[case::plain(prj("exit_code.rs"))]
+#[case::report(features_prj("exit_code.rs", &["report"]))]
+fn exit_code_tests(#[case] mut prj: Project) {
+    prj.set_env(
+        "RSTEST_REPORT_JSON",
+        prj.path().join("report.jsonl").display(),
+    );
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("single")
+        .fail("single_fail")
+        .ok("cases::case_1_pass")
+        .fail("cases::case_2_fail")
+        .assert(output);
+}
+
+#[test]
+fn tags() {
+    let mut prj = features_prj("tags.rs", &["report", "tags"]);
+    let report = prj.path().join("report.jsonl");
+    prj.set_env("RSTEST_TAGS", "db, net, !slow");
+    prj.set_env("RSTEST_REPORT_JSON", report.display());
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("db")
+        .ok("db_slow")
+        .ok("untagged")
+        .ok("cases::case_1_fast")
+        .ok("cases::case_2_slow")
+        .ok("values::n_1_1")
+        .ok("values::n_2_2")
+        .ok("slow_exit_code")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    assert_not_in!(stderr, "SKIPPED db:");
+    assert_not_in!(stderr, "SKIPPED cases::case_1_fast:");
+    assert_in!(
+        stderr,
+        r#"SKIPPED db_slow: tags [db, slow] don't match RSTEST_TAGS="db, net, !slow""#
+    );
+    assert_in!(
+        stderr,
+        r#"SKIPPED untagged: no tags don't match RSTEST_TAGS="db, net, !slow""#
+    );
+    assert_in!(
+        stderr,
+        "SKIPPED cases::case_2_slow: tags [net, slow] don't match"
+    );
+    assert_in!(stderr, "SKIPPED values::n_1_1: no tags don't match");
+    assert_in!(stderr, "SKIPPED values::n_2_2: tags [slow] don't match");
+    assert_in!(stderr, "SKIPPED slow_exit_code: tags [slow] don't match");
+
+    let report = std::fs::read_to_string(report).unwrap();
+    let line = |test: &str| {
+        report
+            .lines()
+            .find(|line| line.contains(&format!(r#""test":"{test}""#)))
+            .unwrap_or_else(|| panic!("missed {test} in report"))
+    };
+    assert_in!(line("db"), r#""tags":["db"],"status":"passed""#);
+    assert_in!(
+        line("db_slow"),
+        r#""tags":["db","slow"],"status":"skipped""#
+    );
+    assert_in!(
+        line("cases::case_1_fast"),
+        r#""tags":["net"],"status":"passed""#
+    );
+}
+
+#[test]
+fn untagged_tests_should_run_without_tags_feature() {
+    let mut prj = prj("tags.rs");
+    prj.set_env("RSTEST_TAGS", "db");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("db")
+        .ok("db_slow")
+        .ok("untagged")
+        .ok("cases::case_1_fast")
+        .ok("cases::case_2_slow")
+        .ok("values::n_1_1")
+        .ok("values::n_2_2")
+        .ok("slow_exit_code")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    assert_not_in!(stderr, "SKIPPED untagged:");
+    assert_not_in!(stderr, "SKIPPED values::n_1_1:");
+    assert_in!(
+        stderr,
+        "SKIPPED cases::case_2_slow: tags [net, slow] don't match"
+    );
+}
+
+#[test]
+fn bench() {
+    let mut prj = prj("bench.rs");
+    let report = prj.path().join("bench_report.tsv");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    prj.set_env("RSTEST_BENCH", "100ms");
+    prj.set_env("RSTEST_BENCH_REPORT", report.display());
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("sort::case_1_small")
+        .ok("sort::case_2_all")
+        .ok("sum::n_1_10")
+        .ok("sum::n_2_100")
+        .ok("fail::case_1_pass")
+        .fail("fail::case_2_fail")
+        .ok("async_bench")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    assert_in!(stderr, "*** BENCH sort::case_1_small: ");
+    assert_in!(stderr, "*** BENCH sum::n_2_100: ");
+    assert_in!(stderr, "*** BENCH async_bench: ");
+    assert_not_in!(stderr, "BENCH fail::case_2_fail");
+
+    let report = std::fs::read_to_string(report).unwrap();
+    let mut lines = report
+        .lines()
+        .map(|line| line.split('\t').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    lines.sort();
+    assert_eq!(
+        vec![
+            "async_bench",
+            "fail::case_1_pass",
+            "sort::case_1_small",
+            "sort::case_2_all",
+            "sum::n_1_10",
+            "sum::n_2_100"
+        ],
+        lines.iter().map(|line| line[0]).collect::<Vec<_>>()
+    );
+    // Fixtures take 5ms but they are not measured
+    let sort_small = &lines[2];
+    assert_eq!(6, sort_small.len());
+    assert!(sort_small[1].parse::<u32>().unwrap() > 1);
+    assert!(sort_small[2].parse::<u64>().unwrap() < 5_000_000);
+}
+
+#[test]
+fn bench_run_as_tests_by_default() {
+    let prj = prj("bench.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("sort::case_1_small")
+        .ok("sort::case_2_all")
+        .ok("sum::n_1_10")
+        .ok("sum::n_2_100")
+        .ok("fail::case_1_pass")
+        .fail("fail::case_2_fail")
+        .ok("async_bench")
+        .assert(output.clone());
+
+    assert_not_in!(output.stderr.str(), "BENCH");
+}
+
+#[test]
+fn repeat() {
+    let prj = prj("repeat.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("fresh_fixtures")
+        .fail("fail_at_iteration")
+        .ok("cases::case_1_single")
+        .ok("cases::case_2_repeated")
+        .ok("concurrent")
+        .fail("concurrent_fail")
+        .fail("async_repeat")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    assert_in!(
+        stderr,
+        "*** REPEAT fail_at_iteration: iteration 7 of 100 failed: Error: \"boom\" ***"
+    );
+    assert_not_in!(output.stdout.str(), "run after the failure");
+    assert_in!(stderr, "*** REPEAT concurrent_fail: iteration ");
+    assert_in!(stderr, "of 100 failed: too many iterations ***");
+    assert_in!(
+        stderr,
+        "*** REPEAT async_repeat: iteration 3 of 3 failed: assertion failed"
+    );
+}
+
+#[test]
+fn retry() {
+    let mut prj = prj("retry.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    let report = prj.path().join("flaky_report.tsv");
+    prj.set_env("RSTEST_FLAKY_REPORT", report.display());
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("flaky")
+        .fail("always_fail")
+        .ok("cases::case_1_retried")
+        .fail("cases::case_2_not_retried")
+        .ok("timeout")
+        .ok("async_flaky")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    assert_in!(
+        stderr,
+        "*** RETRY flaky: attempt 1 of 3 failed: assertion `left == right` failed: attempt 1 failed"
+    );
+    assert_in!(stderr, "*** FLAKY TEST flaky: passed at attempt 3 of 3 ***");
+    assert_in!(
+        stderr,
+        "*** RETRY always_fail: attempt 2 of 3 failed: attempt 2 failed ***"
+    );
+    assert_not_in!(stderr, "RETRY always_fail: attempt 3");
+    assert_in!(output.stdout.str(), "attempt 3 failed");
+    assert_in!(
+        stderr,
+        "*** FLAKY TEST cases::case_1_retried: passed at attempt 2 of 2 ***"
+    );
+    assert_not_in!(stderr, "RETRY cases::case_2_not_retried");
+    assert_in!(stderr, "*** RETRY timeout: attempt 1 of 2 failed: Timeout");
+    assert_in!(
+        stderr,
+        "*** FLAKY TEST async_flaky: passed at attempt 2 of 2 ***"
+    );
+
+    let report = std::fs::read_to_string(report).unwrap();
+    let mut lines = report.lines().collect::<Vec<_>>();
+    lines.sort();
+    assert_eq!(
+        vec![
+            "async_flaky\t2",
+            "cases::case_1_retried\t2",
+            "flaky\t3",
+            "timeout\t2"
+        ],
+        lines
+    );
+}
+
+#[test]
+fn retry_disabled_by_env() {
+    let mut prj = prj("retry.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    prj.set_env("RSTEST_RETRY", "0");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .fail("flaky")
+        .fail("always_fail")
+        .fail("cases::case_1_retried")
+        .fail("cases::case_2_not_retried")
+        .fail("timeout")
+        .fail("async_flaky")
+        .assert(output.clone());
+
+    assert_not_in!(output.stderr.str(), "RETRY");
+}
+
+#[test]
+fn timeout_watchdog() {
+    let prj = prj("timeout_watchdog.rs");
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("single_pass")
+        .fail("single_fail_value")
+        .fail("single_fail_timeout")
+        .ok("group::case_1_pass")
+        .fail("group::case_2_fail_timeout")
+        .fail("unwind_at_checkpoint")
+        .assert(output.clone());
+
+    assert_in!(output.stdout.str(), "Timeout 10ms expired");
+}
+
 mod async_timeout_feature {
     use super::*;
 
@@ -1612,4 +2508,109 @@ mod should_show_correct_errors {
             .unindent()
         );
     }
+
+    #[test]
+    fn if_stable_case_names_are_duplicated() {
+        let (output, name) = execute();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: Duplicate case name: 'case_1' is already used by another case. Use #[case::description(...)] to give it a different name.
+                   --> {}/src/lib.rs:125:8
+                    |
+                125 | #[case(1)]
+                    |        ^
+                ",
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn if_named_case_args_dont_match_case_args() {
+        let (output, name) = execute();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: Positional argument cannot follow named arguments.
+                   --> {}/src/lib.rs:129:15
+                    |
+                129 | #[case(a = 1, c = 2)]
+                    |               ^^^^^
+                ",
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: Missed case arguments: 'a'.
+                   --> {}/src/lib.rs:130:8
+                    |
+                130 | #[case(b = 2)]
+                    |        ^^^^^
+                ",
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: Positional argument cannot follow named arguments.
+                   --> {}/src/lib.rs:131:15
+                    |
+                131 | #[case(a = 1, 2)]
+                    |               ^
+                ",
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn if_xfail_or_retry_are_used_on_unsupported_tests() {
+        let (output, name) = execute();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: #[xfail] supports just tests that return `()` or a `Result`: remove #[xfail] or change the return type
+                   --> {}/src/lib.rs:136:41
+                    |
+                136 | fn error_xfail_unsupported_outcome() -> std::process::ExitCode {{
+                    |                                         ^^^^^^^^^^^^^^^^^^^^^^
+                ",
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: #[retry] cannot be used with #[should_panic]
+                   --> {}/src/lib.rs:143:1
+                    |
+                143 | #[should_panic]
+                    | ^^^^^^^^^^^^^^^
+                ",
+                name
+            )
+            .unindent()
+        );
+    }
 }
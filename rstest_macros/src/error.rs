@@ -1,23 +1,44 @@
 /// Module for error rendering stuff
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use proc_macro2::TokenStream;
+use syn::{parse_quote, visit, ItemFn};
 use syn::{spanned::Spanned, visit::Visit};
-use syn::{visit, ItemFn};
 
 use crate::parse::{
     fixture::FixtureInfo,
+    repeat::Repeat,
+    retry::Retry,
     rstest::{RsTestData, RsTestInfo},
+    runtimes::Runtimes,
+    serial::Keys,
+    skip_if::SkipIf,
+    tag::Tags,
+    trace::Trace,
+    xfail::XFail,
 };
 use crate::refident::MaybeIdent;
 
-use super::utils::fn_args_has_ident;
+use super::utils::{attr_ends_with, attr_is, attr_is_rstest, fn_args_has_ident};
 
 pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
         .chain(duplicate_arguments(info.data.items.iter()))
         .chain(invalid_cases(&info.data))
         .chain(case_args_without_cases(&info.data))
+        .chain(duplicate_case_names(info))
+        .chain(invalid_xfail(test, info))
+        .chain(unsupported_with(test, info, "xfail"))
+        .chain(invalid_skip_if(test, info))
+        .chain(invalid_runtimes(test, info))
+        .chain(invalid_isolate(test, info))
+        .chain(invalid_warn_slow(test, info))
+        .chain(invalid_trace(test, info))
+        .chain(invalid_retry(test, info))
+        .chain(unsupported_with(test, info, "retry"))
+        .chain(invalid_repeat(test, info))
+        .chain(invalid_tag(test, info))
+        .chain(invalid_serial(test, info))
         .map(|e| e.to_compile_error())
         .collect()
 }
@@ -200,10 +221,13 @@ fn duplicate_arguments<'a, I: MaybeIdent + Spanned + 'a>(
 }
 
 fn invalid_cases(params: &RsTestData) -> Errors {
-    let n_args = params.case_args().count();
+    let case_args = params.case_args().cloned().collect::<Vec<_>>();
+    let n_args = case_args.len();
     Box::new(
         params
             .cases()
+            // Cases with named arguments are already checked when they're resolved
+            .filter(move |case| !case.has_named_args(&case_args))
             .filter(move |case| case.args.len() != n_args)
             .map(|case| {
                 syn::Error::new_spanned(
@@ -225,8 +249,235 @@ fn case_args_without_cases(params: &RsTestData) -> Errors {
     Box::new(std::iter::empty())
 }
 
+fn duplicate_case_names(info: &RsTestInfo) -> Errors<'_> {
+    if !info.attributes.stable_names() {
+        return Box::new(std::iter::empty());
+    }
+    let mut used = HashSet::new();
+    Box::new(
+        info.data
+            .cases()
+            .map(|case| (case, case.stable_name()))
+            .filter(move |(_, name)| !used.insert(name.clone()))
+            .map(|(case, name)| {
+                syn::Error::new_spanned(
+                    case,
+                    format!(
+                        "Duplicate case name: '{name}' is already used by another case. \
+                        Use #[case::description(...)] to give it a different name."
+                    ),
+                )
+            }),
+    )
+}
+
+/// All the attributes that will be applied to the rendered tests: the test function ones and
+/// the ones given to cases and values.
+fn tests_attrs<'a>(
+    test: &'a ItemFn,
+    info: &'a RsTestInfo,
+) -> impl Iterator<Item = &'a syn::Attribute> {
+    test.attrs.iter().chain(info.data.tests_attrs())
+}
+
+/// The attributes lists that can be applied to a rendered test together with the key that
+/// tells which of them can be combined: the test function attributes (`None`) are combined
+/// with all the others, two lists with the same key never end up in the same test (i.e.
+/// two cases or two values of the same argument).
+fn tests_attrs_groups<'a>(
+    test: &'a ItemFn,
+    info: &'a RsTestInfo,
+) -> Vec<(Option<usize>, &'a [syn::Attribute])> {
+    std::iter::once((None, test.attrs.as_slice()))
+        .chain(
+            info.data
+                .cases()
+                .map(|case| (Some(0), case.attrs.as_slice())),
+        )
+        .chain(
+            info.data
+                .list_values()
+                .enumerate()
+                .flat_map(|(pos, vlist)| {
+                    vlist
+                        .values
+                        .iter()
+                        .map(move |value| (Some(pos + 1), value.attrs.as_slice()))
+                }),
+        )
+        .collect()
+}
+
+fn is_supported_outcome(output: &syn::Type) -> bool {
+    match output {
+        syn::Type::Tuple(t) => t.elems.is_empty(),
+        syn::Type::Path(p) => p
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string().ends_with("Result"))
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
+/// `#[xfail]` and `#[retry]` need to inspect the test outcome: they support just tests that
+/// return `()` or a `Result` and cannot be applied together with `#[should_panic]`.
+fn unsupported_with<'a>(test: &'a ItemFn, info: &'a RsTestInfo, name: &'a str) -> Errors<'a> {
+    let groups = tests_attrs_groups(test, info);
+    let has = |attrs: &[syn::Attribute], n: &str| attrs.iter().any(|a| attr_is(a, n));
+    if !groups.iter().any(|(_, attrs)| has(attrs, name)) {
+        return Box::new(std::iter::empty());
+    }
+    let output = match &test.sig.output {
+        syn::ReturnType::Type(_, output) => Some(output.as_ref()),
+        syn::ReturnType::Default => None,
+    }
+    .filter(|&output| !is_supported_outcome(output))
+    .map(|output| {
+        syn::Error::new_spanned(
+            output,
+            format!(
+                "#[{name}] supports just tests that return `()` or a `Result`: \
+                    remove #[{name}] or change the return type"
+            ),
+        )
+    });
+    let mut should_panic = Vec::new();
+    for (pos, (key, attrs)) in groups.iter().enumerate() {
+        let combined = groups
+            .iter()
+            .enumerate()
+            .any(|(other_pos, (other_key, other))| {
+                (pos == other_pos || key.is_none() || other_key.is_none() || key != other_key)
+                    && has(other, name)
+            });
+        if combined {
+            should_panic.extend(
+                attrs
+                    .iter()
+                    .filter(|&a| attr_is(a, "should_panic"))
+                    .map(|a| {
+                        syn::Error::new_spanned(
+                            a,
+                            format!("#[{name}] cannot be used with #[should_panic]"),
+                        )
+                    }),
+            );
+        }
+    }
+    Box::new(output.into_iter().chain(should_panic))
+}
+
+fn invalid_xfail<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "xfail"))
+            .filter_map(|a| XFail::from_attr(a).err()),
+    )
+}
+
+fn invalid_skip_if<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "skip_if"))
+            .filter_map(|a| a.parse_args::<SkipIf>().err()),
+    )
+}
+
+fn invalid_isolate<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "isolate") && !matches!(a.meta, syn::Meta::Path(_)))
+            .map(|a| syn::Error::new_spanned(a, "#[isolate] doesn't take any argument")),
+    )
+}
+
+fn invalid_warn_slow<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "warn_slow"))
+            .filter_map(|a| a.parse_args::<syn::Expr>().err()),
+    )
+}
+
+fn invalid_trace<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "trace"))
+            .filter_map(|a| Trace::from_attr(a).err()),
+    )
+}
+
+fn invalid_retry<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "retry"))
+            .filter_map(|a| a.parse_args::<Retry>().err()),
+    )
+}
+
+fn invalid_repeat<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    let is_async = test.sig.asyncness.is_some();
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "repeat"))
+            .filter_map(move |a| match a.parse_args::<Repeat>() {
+                Err(err) => Some(err),
+                Ok(Repeat {
+                    threads: Some(_), ..
+                }) if is_async => Some(syn::Error::new_spanned(
+                    a,
+                    "#[repeat(threads = ...)] is not supported by async tests",
+                )),
+                Ok(_) => None,
+            }),
+    )
+}
+
+fn invalid_tag<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "tag"))
+            .filter_map(|a| a.parse_args::<Tags>().err()),
+    )
+}
+
+fn invalid_serial<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is_rstest(a, "serial") || attr_is_rstest(a, "parallel"))
+            .filter_map(|a| Keys::from_attr(a).err()),
+    )
+}
+
+fn invalid_runtimes<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    let is_async = test.sig.asyncness.is_some();
+    let injected = test
+        .attrs
+        .iter()
+        .any(|a| attr_ends_with(a, &parse_quote! {test}));
+    Box::new(
+        tests_attrs(test, info)
+            .filter(|&a| attr_is(a, "runtimes"))
+            .filter_map(move |a| match a.parse_args::<Runtimes>() {
+                Err(err) => Some(err),
+                Ok(_) if !is_async => Some(syn::Error::new_spanned(
+                    a,
+                    "#[runtimes] can be used just on async tests",
+                )),
+                Ok(_) if injected => Some(syn::Error::new_spanned(
+                    a,
+                    "#[runtimes] cannot be used with an injected test attribute",
+                )),
+                Ok(_) => None,
+            }),
+    )
+}
+
 #[cfg(test)]
 mod test {
+    use crate::parse::{testcase::TestCase, vlist::ValueList, Attribute};
     use crate::test::{assert_eq, *};
     use rstest_test::assert_in;
 
@@ -268,4 +519,313 @@ mod test {
 
         assert_eq!(0, errors.count());
     }
+
+    #[test]
+    fn duplicate_case_names_should_return_error_only_with_stable_names() {
+        let mut info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").into(),
+            TestCase::from("2").into(),
+            TestCase::from("1").into(),
+            TestCase::from("3").with_description("x").into(),
+            TestCase::from("x").into(),
+        ])
+        .into();
+
+        assert_eq!(0, duplicate_case_names(&info).count());
+
+        info.attributes.append(Attribute::attr("stable_names"));
+        let errors = duplicate_case_names(&info)
+            .map(|e| format!("{:?}", e))
+            .collect::<Vec<_>>();
+
+        assert_eq!(2, errors.len());
+        assert_in!(errors[0], "Duplicate case name: 'case_1'");
+        assert_in!(errors[1], "Duplicate case name: 'case_x'");
+    }
+
+    #[test]
+    fn invalid_xfail_should_check_test_and_cases_attributes() {
+        let test: ItemFn = r#"#[xfail(wrong)] #[xfail(reason = "ok")] fn f() {}"#.ast();
+        let mut info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1")
+                .with_attrs(attrs("#[xfail(reason = 42)]"))
+                .into(),
+            TestCase::from("2").with_attrs(attrs("#[xfail]")).into(),
+        ])
+        .into();
+
+        assert_eq!(2, invalid_xfail(&test, &info).count());
+
+        info.data.items.truncate(1);
+        assert_eq!(1, invalid_xfail(&test, &info).count());
+    }
+
+    #[test]
+    fn invalid_skip_if_should_check_test_and_cases_attributes() {
+        let test: ItemFn = r#"#[skip_if()] #[skip_if(true, reason = "ok")] fn f() {}"#.ast();
+        let mut info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1")
+                .with_attrs(attrs("#[skip_if(a > 0, because = 42)]"))
+                .into(),
+            TestCase::from("2")
+                .with_attrs(attrs("#[skip_if(a > 1)]"))
+                .into(),
+        ])
+        .into();
+
+        assert_eq!(2, invalid_skip_if(&test, &info).count());
+
+        info.data.items.truncate(1);
+        assert_eq!(1, invalid_skip_if(&test, &info).count());
+    }
+
+    #[test]
+    fn invalid_xfail_should_check_values_attributes() {
+        let test: ItemFn = "fn f(v: u32) {}".ast();
+        let info: RsTestInfo =
+            RsTestData::from(vec!["v => [#[xfail(wrong)] 1, #[xfail] 2, #[skip_if()] 3]"
+                .ast::<ValueList>()
+                .into()])
+            .into();
+
+        assert_eq!(1, invalid_xfail(&test, &info).count());
+        assert_eq!(1, invalid_skip_if(&test, &info).count());
+    }
+
+    #[rstest]
+    #[case::valid("#[warn_slow(Duration::from_millis(200))]", 0)]
+    #[case::missed_threshold("#[warn_slow]", 1)]
+    #[case::invalid_expression("#[warn_slow(<some>)]", 1)]
+    fn invalid_warn_slow_should_check_test_and_cases_attributes(
+        #[case] warn_slow: &str,
+        #[case] errors: usize,
+    ) {
+        let test: ItemFn = "fn f() {}".ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs(warn_slow)).into(),
+        ])
+        .into();
+
+        assert_eq!(errors, invalid_warn_slow(&test, &info).count());
+    }
+
+    #[rstest]
+    #[case::valid("", "#[repeat(10, threads = 2)]", 0)]
+    #[case::invalid("", "#[repeat(0)]", 1)]
+    #[case::async_sequential("async", "#[repeat(10)]", 0)]
+    #[case::async_threads("async", "#[repeat(10, threads = 2)]", 1)]
+    fn invalid_repeat_should_check_test_and_cases_attributes(
+        #[case] asyncness: &str,
+        #[case] repeat: &str,
+        #[case] errors: usize,
+    ) {
+        let test: ItemFn = format!("{asyncness} fn f() {{}}").ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs(repeat)).into(),
+        ])
+        .into();
+
+        assert_eq!(errors, invalid_repeat(&test, &info).count());
+    }
+
+    #[test]
+    fn invalid_serial_should_check_test_and_cases_attributes() {
+        let test: ItemFn =
+            r#"#[rstest::serial] #[rstest::parallel("db")] #[serial("db")] fn f() {}"#.ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1")
+                .with_attrs(attrs("#[rstest::serial(db)]"))
+                .into(),
+            TestCase::from("2")
+                .with_attrs(attrs("#[rstest::parallel = db]"))
+                .into(),
+        ])
+        .into();
+
+        assert_eq!(2, invalid_serial(&test, &info).count());
+    }
+
+    #[test]
+    fn invalid_tag_should_check_test_cases_and_values_attributes() {
+        let test: ItemFn = "#[tag(slow)] #[tag] fn f() {}".ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs("#[tag(db)]")).into(),
+            TestCase::from("2").with_attrs(attrs("#[tag(1)]")).into(),
+        ])
+        .into();
+
+        assert_eq!(2, invalid_tag(&test, &info).count());
+
+        let test: ItemFn = "fn f(v: u32) {}".ast();
+        let info: RsTestInfo = RsTestData::from(vec!["v => [#[tag(a)] 1, #[tag()] 2]"
+            .ast::<ValueList>()
+            .into()])
+        .into();
+
+        assert_eq!(1, invalid_tag(&test, &info).count());
+    }
+
+    #[test]
+    fn invalid_retry_should_check_test_and_cases_attributes() {
+        let test: ItemFn = "#[retry(times)] #[retry(2)] fn f() {}".ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs("#[retry]")).into(),
+            TestCase::from("2").with_attrs(attrs("#[retry(1)]")).into(),
+        ])
+        .into();
+
+        assert_eq!(2, invalid_retry(&test, &info).count());
+    }
+
+    #[rstest]
+    #[case::unit("fn f() {}", 0)]
+    #[case::explicit_unit("fn f() -> () {}", 0)]
+    #[case::result("fn f() -> Result<(), String> {}", 0)]
+    #[case::result_path("fn f() -> std::io::Result<()> {}", 0)]
+    #[case::result_alias("fn f() -> TestResult {}", 0)]
+    #[case::exit_code("fn f() -> std::process::ExitCode {}", 1)]
+    #[case::impl_termination("fn f() -> impl std::process::Termination {}", 1)]
+    fn xfail_and_retry_should_support_just_unit_and_result_outcomes(
+        #[case] f: &str,
+        #[case] expected: usize,
+        #[values("xfail", "retry")] name: &str,
+    ) {
+        let test: ItemFn = format!("#[{name}] {f}").ast();
+        let info: RsTestInfo = Default::default();
+
+        let errors = unsupported_with(&test, &info, name)
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, errors.len());
+        for error in errors {
+            assert_in!(error, format!("#[{name}] supports just tests that return"));
+        }
+    }
+
+    #[test]
+    fn unsupported_return_type_should_be_reported_just_if_attribute_is_used() {
+        let test: ItemFn = "fn f() -> std::process::ExitCode {}".ast();
+        let info: RsTestInfo = Default::default();
+
+        assert_eq!(0, unsupported_with(&test, &info, "xfail").count());
+    }
+
+    #[rstest]
+    #[case::both_on_test("#[xfail] #[should_panic] fn f(a: u32) {}", &[], 1)]
+    #[case::on_test_and_case("#[xfail] fn f(a: u32) {}", &["#[should_panic]", ""], 1)]
+    #[case::on_case_and_test("#[should_panic] fn f(a: u32) {}", &["#[xfail]", ""], 1)]
+    #[case::both_on_case("fn f(a: u32) {}", &["#[xfail] #[should_panic]", ""], 1)]
+    #[case::on_different_cases("fn f(a: u32) {}", &["#[xfail]", "#[should_panic]"], 0)]
+    #[case::just_should_panic("#[should_panic] fn f(a: u32) {}", &["", ""], 0)]
+    fn xfail_and_retry_cannot_be_combined_with_should_panic(
+        #[case] f: &str,
+        #[case] cases_attrs: &[&str],
+        #[case] expected: usize,
+        #[values("xfail", "retry")] name: &str,
+    ) {
+        let test: ItemFn = f.replace("xfail", name).ast();
+        let info: RsTestInfo = RsTestData::from(
+            std::iter::once(ident("a").into())
+                .chain(cases_attrs.iter().enumerate().map(|(pos, a)| {
+                    TestCase::from(pos.to_string().as_str())
+                        .with_attrs(attrs(&a.replace("xfail", name)))
+                        .into()
+                }))
+                .collect::<Vec<_>>(),
+        )
+        .into();
+
+        let errors = unsupported_with(&test, &info, name)
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, errors.len());
+        for error in errors {
+            assert_in!(
+                error,
+                format!("#[{name}] cannot be used with #[should_panic]")
+            );
+        }
+    }
+
+    #[test]
+    fn should_panic_on_values_should_be_combined_with_cases() {
+        let test: ItemFn = "fn f(a: u32, v: u32) {}".ast();
+        let mut vlist = values_list("v", &["1", "2"]);
+        vlist.values[1].attrs = attrs("#[should_panic]");
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs("#[xfail]")).into(),
+            vlist.into(),
+        ])
+        .into();
+
+        assert_eq!(1, unsupported_with(&test, &info, "xfail").count());
+    }
+
+    #[rstest]
+    #[case::valid("#[isolate]", 0)]
+    #[case::with_arguments("#[isolate(timeout)]", 1)]
+    #[case::with_value("#[isolate = true]", 1)]
+    fn invalid_isolate_should_check_test_and_cases_attributes(
+        #[case] isolate: &str,
+        #[case] errors: usize,
+    ) {
+        let test: ItemFn = "fn f() {}".ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs(isolate)).into(),
+        ])
+        .into();
+
+        assert_eq!(errors, invalid_isolate(&test, &info).count());
+    }
+
+    #[rstest]
+    #[case::valid("#[trace]", 0)]
+    #[case::on_failure("#[trace(on_failure)]", 0)]
+    #[case::fixtures("#[trace(fixtures)]", 0)]
+    #[case::invalid_argument("#[trace(always)]", 1)]
+    fn invalid_trace_should_check_test_and_cases_attributes(
+        #[case] trace: &str,
+        #[case] errors: usize,
+    ) {
+        let test: ItemFn = "fn f() {}".ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs(trace)).into(),
+        ])
+        .into();
+
+        assert_eq!(errors, invalid_trace(&test, &info).count());
+    }
+
+    #[rstest]
+    #[case::valid("async fn f() {}", "#[runtimes(tokio, smol)]", 0)]
+    #[case::invalid("async fn f() {}", "#[runtimes(actix)]", 1)]
+    #[case::not_async("fn f() {}", "#[runtimes(tokio)]", 1)]
+    #[case::injected("#[actix_rt::test] async fn f() {}", "#[runtimes(tokio)]", 1)]
+    fn invalid_runtimes_should_check_test_and_cases_attributes(
+        #[case] test: &str,
+        #[case] runtimes: &str,
+        #[case] errors: usize,
+    ) {
+        let test: ItemFn = test.ast();
+        let info: RsTestInfo = RsTestData::from(vec![
+            ident("a").into(),
+            TestCase::from("1").with_attrs(attrs(runtimes)).into(),
+        ])
+        .into();
+
+        assert_eq!(errors, invalid_runtimes(&test, &info).count());
+    }
 }
@@ -557,6 +557,70 @@ pub fn fixture(
 /// test result: ok. 4 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out
 /// ```
 ///
+/// ### Named case arguments
+///
+/// In long cases lists it's easy to swap two arguments of the same type: you can
+/// also give case values by name with `name = value`. Like in function calls positional
+/// values should precede the named ones and every `#[case]` argument should be given
+/// exactly once. An assignment to any other name, like `#[case(x = 1)]`, is still a
+/// positional value.
+///
+/// ```rust
+/// # use rstest::*;
+/// #[rstest]
+/// #[case(input = 0, expected = 0)]
+/// #[case(expected = 1, input = 1)]
+/// #[case(2, expected = 1)]
+/// fn fibonacci_test(#[case] input: u32,#[case] expected: u32) {
+///     assert_eq!(expected, fibonacci(input))
+/// }
+/// # fn fibonacci(input: u32) -> u32 {
+/// #     match input {
+/// #         0 => 0,
+/// #         1 => 1,
+/// #         n => fibonacci(n - 2) + fibonacci(n - 1)
+/// #     }
+/// # }
+/// ```
+///
+/// ### Stable case names
+///
+/// By default cases are named by their position, so adding a case in the middle of the
+/// list renames all the following ones. With the `stable_names` option every case
+/// without a description is named after its arguments instead: names that are too long
+/// are truncated and completed by an hash of the arguments' code.
+///
+/// ```rust
+/// # use rstest::*;
+/// #[rstest(stable_names)]
+/// #[case::zero_base_case(0, 0)]
+/// #[case(2, 1)]
+/// #[case(3, 2)]
+/// fn fibonacci_test(#[case] input: u32,#[case] expected: u32) {
+///     assert_eq!(expected, fibonacci(input))
+/// }
+/// # fn fibonacci(input: u32) -> u32 {
+/// #     match input {
+/// #         0 => 0,
+/// #         1 => 1,
+/// #         n => fibonacci(n - 2) + fibonacci(n - 1)
+/// #     }
+/// # }
+/// ```
+///
+/// Output will be
+/// ```text
+/// running 3 tests
+/// test fibonacci_test::case_zero_base_case ... ok
+/// test fibonacci_test::case_2_1 ... ok
+/// test fibonacci_test::case_3_2 ... ok
+///
+/// test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out
+/// ```
+///
+/// If two cases end up with the same name you'll get a compile error: use a description
+/// to disambiguate them.
+///
 /// ### Use specific `case` attributes
 ///
 /// Every function's attributes that preceding a `#[case]` attribute will
@@ -662,6 +726,23 @@ pub fn fixture(
 /// Note that the test names contains the given expression sanitized into
 /// a valid Rust identifier name. This should help to identify which case fails.
 ///
+/// You can give a name to a value with `name => value`: the name is used in the test
+/// name instead of the expression. Like cases, every value can also have its own
+/// attributes that are applied just to the tests that use it.
+///
+/// ```rust
+/// # use rstest::*;
+/// #[rstest]
+/// fn increment(
+///     #[values(zero => 0, one => 1, #[should_panic] overflow => u32::MAX)]
+///     value: u32
+/// ) {
+///     assert!(value + 1 > value)
+/// }
+/// ```
+///
+/// Here the tests are `value_1_zero`, `value_2_one` and `value_3_overflow`, and just the
+/// last one is marked with `#[should_panic]`.
 ///
 /// Also value list implements the magic conversion feature: every time the value type
 /// implements `FromStr` trait you can use a literal string to define it.
@@ -675,6 +756,27 @@ pub fn fixture(
 /// }
 /// ```
 ///
+/// ### Flat test names
+///
+/// Every values list adds a module level to the test names. With the `flat` option all
+/// the combinations are rendered as sibling functions with the names joined by `__`:
+///
+/// ```rust
+/// # use rstest::*;
+/// # fn valid_user(name: &str, age: u8) -> bool { true }
+/// #[rstest(flat)]
+/// fn should_accept_all_corner_cases(
+///     #[values("J", "A")] name: &str,
+///     #[values(14, 100)] age: u8
+/// ) {
+///     assert!(valid_user(name, age))
+/// }
+/// ```
+///
+/// Here the tests are named like `should_accept_all_corner_cases::name_1___J____age_2_100`.
+/// Cases are flattened in the same way (e.g. `case_1_description__age_1_14`). If two
+/// combinations end up with the same name you'll get a compile error.
+///
 /// ## Files path as input arguments
 ///
 /// If you need to create a test for each file in a given location you can use
@@ -703,6 +805,36 @@ pub fn fixture(
 /// `valid_call.yaml` in the folder `../test_cases` (from your crate root) a test name could be
 /// `path_1__UP_test_cases_valid_call_yaml`.
 ///
+/// ## Destructuring Arguments
+///
+/// Fixtures, cases and values arguments can be any irrefutable pattern: the test body
+/// can use the destructured bindings directly.
+///
+/// ```rust
+/// # use rstest::*;
+/// # struct Point { x: i32, y: i32 }
+/// #[fixture]
+/// fn pair() -> (u32, u32) { (21, 2) }
+///
+/// #[rstest]
+/// #[case((1, 2), 3)]
+/// fn destructured(
+///     #[from(pair)] (a, b): (u32, u32),
+///     #[case] (c, d): (u32, u32),
+///     #[case] expected: u32,
+///     #[values(Point { x: 1, y: 1 })] Point { x, y }: Point,
+/// ) {
+///     assert_eq!(42, a * b);
+///     assert_eq!(expected, c + d);
+///     assert_eq!(x, y);
+/// }
+/// ```
+///
+/// Every destructured argument needs a name to be resolved or to build the test names:
+/// by default it's `arg_<position>` (`arg_1` for the first function argument) but you
+/// can choose a different one with `#[name = "..."]`. Fixtures are resolved by this
+/// name, so use `#[from(...)]` to inject a fixture in a pattern.
+///
 /// ## Use Parametrize definition in more tests
 ///
 /// If you need to use a test list for more than one test you can use
@@ -759,6 +891,39 @@ pub fn fixture(
 /// async-std = { version = "1.5", features = ["attributes"] }
 /// ```
 ///
+/// You can change the default runtime by enabling one of the `tokio`, `tokio-multi-thread`
+/// or `smol` features of `rstest`:
+///
+/// ```toml
+/// rstest = { version = "...", features = ["tokio"] }
+/// ```
+///
+/// `tokio` uses `#[tokio::test]`, `tokio-multi-thread` uses
+/// `#[tokio::test(flavor = "multi_thread")]` and `smol` runs the test body in
+/// `smol::block_on()`. If more than one is enabled `tokio` wins over `smol`.
+///
+/// You can also run the same async test on more runtimes with `#[runtimes(...)]`:
+/// `rstest` renders a test for each runtime (`async_std`, `tokio`, `tokio_multi_thread`
+/// or `smol`) and names it by the runtime, like it does for a values list.
+///
+/// ```rust
+/// use rstest::*;
+/// # async fn async_sum(a: u32, b: u32) -> u32 { a + b }
+///
+/// #[rstest]
+/// #[case(5, 2, 3)]
+/// #[case(3, 1, 2)]
+/// #[runtimes(tokio, async_std)]
+/// async fn my_async_test(#[case] expected: u32, #[case] a: u32, #[case] b: u32) {
+///     assert_eq!(expected, async_sum(a, b).await);
+/// }
+/// ```
+///
+/// Will generate `my_async_test::case_1::tokio`, `my_async_test::case_1::async_std` and
+/// so on. You need the runtimes' crates in your dev dependencies; `#[timeout]` works
+/// with any runtime. Like any other attribute, put `#[runtimes]` after the cases if you
+/// want to apply it to all of them.
+///
 /// If your test input is an async value (fixture or test parameter) you can use `#[future]`
 /// attribute to remove `impl Future<Output = T>` boilerplate and just use `T`:
 ///
@@ -804,7 +969,11 @@ pub fn fixture(
 /// ### Default timeout
 ///
 /// You can set a default timeout for test using the `RSTEST_TIMEOUT` environment variable.
-/// The value is in seconds and is evaluated on test compile time.///
+/// The value is read when the test runs, so you can change it without recompiling: use a
+/// number of seconds or a duration with a unit like `500ms`, `30s`, `2m` or `1h`. Sync tests
+/// run on another thread like with `#[timeout]` and resolve their arguments there, but the
+/// tests with an output that isn't `Send` and `'static` run without the default timeout: use
+/// `#[timeout(<duration>, watchdog)]` for them. Async tests need the `async-timeout` feature.
 ///
 /// ### Test `#[timeout()]`
 ///
@@ -862,6 +1031,305 @@ pub fn fixture(
 /// If you want to use `timeout` for `async` test you need to use `async-timeout`
 /// feature (enabled by default).
 ///
+/// The timeout applies also to fixtures resolution, and when it expires the panic message
+/// tells the phase that hung: ``while resolving fixture `db` ``, ``while awaiting
+/// `#[future(awt)]` arguments`` or `while running the test body`.
+/// The phases are tracked only for tests with a `#[timeout]` attribute.
+/// The panic doesn't show where the test was stuck: the standard library cannot capture the
+/// backtrace of another thread. A watchdog timeout panics in the test thread, but only at
+/// the next checkpoint or when the test returns, so its backtrace points there; a test that
+/// is really stuck is aborted without any backtrace.
+///
+/// Sync tests with a timeout run in a new thread, so their inputs and output should be
+/// `Send` and `'static`. Use `#[timeout(<duration>, watchdog)]` to run the test on the
+/// current thread under a watchdog instead: when the timeout expires the watchdog reports
+/// it and the test panics at the next `rstest::timeout::checkpoint()` call or when it
+/// returns. If the test doesn't reach a checkpoint in one second the watchdog aborts the
+/// test process.
+///
+/// ```rust
+/// # use rstest::*;
+/// # use std::{rc::Rc, time::Duration};
+/// # fn steps() -> impl Iterator<Item = u32> { 0..10 }
+/// #[rstest]
+/// #[timeout(Duration::from_millis(100), watchdog)]
+/// fn not_send(#[values(Rc::new(42))] shared: Rc<u32>) {
+///     for _ in steps() {
+///         rstest::timeout::checkpoint();
+///         assert_eq!(42, *shared);
+///     }
+/// }
+/// ```
+///
+/// ### Warn slow tests
+///
+/// A soft threshold: tests that take more than `#[warn_slow(<duration>)]` still pass but
+/// print a highlighted warning with the elapsed time and the test name. Like `timeout` you
+/// can use it on the whole test or just on some cases. If you enable the `warn-slow` feature
+/// you can set a default threshold for all tests with the `RSTEST_WARN_SLOW` environment
+/// variable (i.e. `200ms`). Failed tests never warn.
+///
+/// ```rust
+/// # use rstest::*;
+/// # use std::time::Duration;
+/// # fn sort(v: &mut [u32]) { v.sort() }
+/// #[rstest]
+/// #[case::small(vec![3, 2, 1])]
+/// #[case::big((0..100_000).rev().collect())]
+/// #[warn_slow(Duration::from_millis(200))]
+/// fn sort_values(#[case] mut values: Vec<u32>) {
+///     sort(&mut values);
+/// }
+/// ```
+///
+/// If `RSTEST_SLOW_REPORT` environment variable contains a file path, each slow test
+/// appends a tab separated line with its name, the elapsed and threshold milliseconds.
+///
+/// ### Isolate tests in a child process
+///
+/// A timed out test panics but its thread keeps running, and an abort or a segfault
+/// (i.e. in FFI code) kills the whole test binary. Mark a test, or just some cases, with
+/// `#[isolate]` to run it in a child process of the same test binary: the test's
+/// fixtures are resolved in the child, on timeout the child process is killed and a
+/// signal is reported as a test failure with the child output.
+///
+/// ```rust
+/// # use rstest::*;
+/// # use std::time::Duration;
+/// # fn unsafe_ffi_call() {}
+/// #[rstest]
+/// #[isolate]
+/// #[timeout(Duration::from_secs(1))]
+/// fn call_ffi() {
+///     unsafe_ffi_call();
+/// }
+/// ```
+///
+/// If you enable the `isolate` feature, you can isolate all tests by setting the
+/// `RSTEST_ISOLATE` environment variable to `1` when you run them.
+///
+/// ## Expected failures
+///
+/// You can mark a test, or just some cases, with `#[xfail]` to track a known bug: the
+/// test passes if it fails, otherwise it fails with an `XPASS` message. Unlike
+/// `#[should_panic]`, also a returned `Err` or an expired timeout count as failures.
+///
+/// ```rust
+/// # use rstest::*;
+/// # fn parse(s: &str) -> Result<u32, std::num::ParseIntError> { s.parse() }
+/// #[rstest]
+/// #[case("42")]
+/// #[xfail(reason = "sign not supported yet")]
+/// #[case("+42")]
+/// fn parse_number(#[case] input: &str) -> Result<(), std::num::ParseIntError> {
+///     assert_eq!(42, parse(input)?);
+///     Ok(())
+/// }
+/// ```
+///
+/// Use `#[xfail(strict = false)]` if an unexpected pass should just print a warning
+/// instead of failing the test. `#[xfail]` works just with tests that return `()` or a
+/// `Result` and cannot be combined with `#[should_panic]`: both are compile errors.
+///
+/// ## Benchmarks
+///
+/// With the `bench` option (`#[rstest(bench)]`) every case and values combination is
+/// also a benchmark: the inputs stay in sync with the tests. The tests run once as usual,
+/// but if the `RSTEST_BENCH` environment variable is set to a measurement time (i.e. `3s`)
+/// each test runs again and again for that time and prints a `BENCH` line with mean,
+/// standard deviation, min and max time of the calls. The fixtures are resolved for every
+/// call but just the test function call is measured, and the first call is a warm up.
+///
+/// ```rust
+/// # use rstest::*;
+/// #[fixture]
+/// fn data() -> Vec<u32> {
+///     (0..10_000).rev().collect()
+/// }
+///
+/// #[rstest(bench)]
+/// #[case::small(10)]
+/// #[case::all(10_000)]
+/// fn sort(data: Vec<u32>, #[case] len: usize) {
+///     let mut values = data[..len].to_vec();
+///     values.sort();
+/// }
+/// ```
+///
+/// Run the benchmarks one at a time and with optimizations (i.e.
+/// `RSTEST_BENCH=3s cargo test --release sort -- --test-threads=1`). If
+/// `RSTEST_BENCH_REPORT` environment variable contains a file path, each benchmark appends
+/// a tab separated line with its name, the iterations and the mean, standard deviation, min
+/// and max nanoseconds.
+///
+/// ## Retry flaky tests
+///
+/// Mark a test, or just some cases, with `#[retry(n)]` to run it again, up to `n` times,
+/// while it fails: the test passes if any attempt passes and each failed attempt prints
+/// a `RETRY` message with its failure. Every attempt resolves the fixtures again (but
+/// `#[once]` fixtures are built just once) and gets its own `#[timeout]`.
+///
+/// ```rust
+/// # use rstest::*;
+/// # fn fetch(url: &str) -> Result<u32, String> { Ok(200) }
+/// #[rstest]
+/// #[retry(3)]
+/// fn remote_service() -> Result<(), String> {
+///     assert_eq!(200, fetch("http://localhost:8080/health")?);
+///     Ok(())
+/// }
+/// ```
+///
+/// A test that passes after some failed attempts prints a `FLAKY TEST` message and, if
+/// `RSTEST_FLAKY_REPORT` environment variable contains a file path, appends a tab separated
+/// line with its name and the passing attempt. Set `RSTEST_RETRY` to `0` to disable all
+/// retries (i.e. in CI). Like `#[xfail]`, `#[retry]` works just with tests that return `()`
+/// or a `Result` and cannot be combined with `#[should_panic]`.
+///
+/// ## Repeat tests
+///
+/// Use `#[repeat(n)]` to run a test, or just some cases, `n` times: every iteration
+/// resolves the fixtures again (but `#[once]` fixtures are built just once) and gets its own
+/// `#[timeout]`. The test stops at the first failing iteration and prints a `REPEAT`
+/// message with the iteration number.
+///
+/// Sync tests can run the iterations concurrently with `#[repeat(n, threads = t)]`: useful
+/// to shake out ordering bugs in concurrent code.
+///
+/// ```rust
+/// # use rstest::*;
+/// # use std::sync::Arc;
+/// # struct Queue; impl Queue { fn new() -> Self { Queue } fn push(&self, v: u32) {} fn pop(&self) -> Option<u32> { Some(1) } }
+/// #[fixture]
+/// fn queue() -> Arc<Queue> {
+///     Arc::new(Queue::new())
+/// }
+///
+/// #[rstest]
+/// #[repeat(100, threads = 4)]
+/// fn push_and_pop(queue: Arc<Queue>) {
+///     queue.push(42);
+///     assert_eq!(Some(42), queue.pop());
+/// }
+/// ```
+///
+/// ## Skip tests at runtime
+///
+/// `#[ignore]` is decided at compile time: use `#[skip_if(condition, reason = "...")]`
+/// to skip a test, or just some cases, when a runtime condition holds. The condition is
+/// evaluated before resolving any fixture; `reason` is optional and defaults to the
+/// condition code.
+///
+/// ```rust
+/// # use rstest::*;
+/// # struct Database;
+/// # #[fixture] fn db() -> Database { Database }
+/// #[rstest]
+/// #[skip_if(std::env::var("DATABASE_URL").is_err(), reason = "no database")]
+/// fn query(db: Database) {
+///     // ...
+/// }
+/// ```
+///
+/// You can also use `#[skip_if]` on an argument to skip just some values: the
+/// argument is resolved before evaluating the condition, so the condition can use it.
+///
+/// ```rust
+/// # use rstest::*;
+/// # fn system_page_size() -> usize { 4096 }
+/// #[rstest]
+/// fn page_size(
+///     #[values(4096, 16384, 65536)]
+///     #[skip_if(size > system_page_size(), reason = "unsupported page size")]
+///     size: usize,
+/// ) {
+///     // ...
+/// }
+/// ```
+///
+/// The standard test harness has no skipped status: a skipped test passes and prints
+/// `SKIPPED <test name>: <reason>` on the standard error. A skipped test returns the value
+/// given by `rstest::outcome::Passed` (implemented for `()`, `Result` and `ExitCode`): if
+/// the test output type doesn't implement it the test fails instead.
+///
+/// ## Tags
+///
+/// Mark tests, cases and values with `#[tag(name, ...)]` to select them at runtime by the
+/// `RSTEST_TAGS` environment variable: the tags of a case or a value combine with the
+/// function ones. `RSTEST_TAGS` is a comma separated list of tags, `!tag` excludes a tag: a
+/// test runs if it has at least one of the listed tags (if any) and none of the excluded
+/// ones, otherwise it's skipped with a `SKIPPED` message.
+///
+/// ```rust
+/// # use rstest::*;
+/// #[rstest]
+/// #[case::local("localhost")]
+/// #[tag(slow)]
+/// #[case::remote("db.example.com")]
+/// #[tag(db)]
+/// fn query(#[case] host: &str) {
+///     // ...
+/// }
+/// ```
+///
+/// Here `RSTEST_TAGS="db,!slow"` runs just `query::case_1_local`. Untagged tests always run,
+/// unless you enable the `tags` feature: then they are skipped if `RSTEST_TAGS` lists some
+/// tags to run. Tags are also included in the test reports and in the `registry`.
+///
+/// ## Serial tests
+///
+/// Tests that use the same global resource (environment variables, current directory, a
+/// port...) cannot run concurrently: mark them with `#[rstest::serial(key)]` to run them one
+/// at a time. Tests marked by `#[rstest::parallel(key)]` can run together but never with the
+/// `#[rstest::serial(key)]` ones. The attributes take more keys (i.e.
+/// `#[rstest::serial(env, cwd)]`) and without keys use a default one. They can be applied to
+/// cases and values too. The path is required: a bare `#[serial]` is left to other crates
+/// like `serial_test`.
+///
+/// ```rust
+/// # use rstest::*;
+/// #[rstest]
+/// #[rstest::serial(env)]
+/// fn set_home() {
+///     std::env::set_var("HOME", "/tmp");
+///     // ...
+/// }
+///
+/// #[rstest]
+/// #[rstest::parallel(env)]
+/// fn read_home() {
+///     assert!(std::env::var("HOME").is_ok());
+/// }
+/// ```
+///
+/// The locks are process wide: they are taken before resolving the fixtures and released
+/// after their teardown, isolated tests take them in the parent process. Async tests wait
+/// for them without blocking the executor.
+///
+/// ## Test reports
+///
+/// The test harness output doesn't know which function a test comes from or which
+/// parameters it uses. Enable the `report` feature and set `RSTEST_REPORT_JSON`
+/// environment variable to the path of a file where each `rstest` test appends a JSON line
+/// like
+///
+/// ```json
+/// {"suite":"my_crate","test":"tests::sum::case_2_big","function":"sum","case":"big","parameters":{"a":"1_000","b":"2"},"tags":["slow"],"status":"failed","duration":0.0012,"message":"boom"}
+/// ```
+///
+/// `parameters` contains the `#[case]` arguments and the `#[values]` chosen as written in
+/// the source code (or the value name, if any), and the path of `#[files]` arguments
+/// relative to their base directory. `tags` are the test `#[tag]`s, `status` is `passed`,
+/// `failed` or `skipped` (by `#[skip_if]` or `RSTEST_TAGS`) and `message` holds the failure
+/// message or the skip reason. Tests that return neither `()` nor a `Result` are reported
+/// as failed only if they panic.
+///
+/// Set `RSTEST_REPORT_JUNIT` to a directory to write a JUnit XML report for each test
+/// binary: the test function, the case description, the parameters and the tags are
+/// reported as test case properties. Both reports are written when the test runs: with the
+/// `report` feature enabled no recompilation is needed. `#[isolate]` tests are reported by
+/// the parent process.
+///
 /// ## Inject Test Attribute
 ///
 /// If you would like to use another `test` attribute for your test you can simply
@@ -971,8 +1439,10 @@ pub fn fixture(
 /// ```
 /// will trace just `case_2` input arguments.
 ///
-/// If you want to trace input arguments but skip some of them that don't
-/// implement the `Debug` trait, you can also use the
+/// Arguments are printed with `Debug` if they implement it, with `Display` otherwise,
+/// and just by their type name followed by `(unprintable)` if they implement neither.
+///
+/// If you want to trace input arguments but skip some of them, you can also use the
 /// `#[notrace]` argument attribute to skip them:
 ///
 /// ```
@@ -985,6 +1455,65 @@ pub fn fixture(
 ///     assert_eq!(42, injected)
 /// }
 /// ```
+///
+/// With `#[trace(on_failure)]` the arguments are formatted before the test starts but
+/// printed only if the test panics or returns an `Err`, together with the test name:
+///
+/// ```
+/// # use rstest::*;
+/// #[rstest]
+/// #[case::small(1)]
+/// #[case::big(1_000_000)]
+/// #[trace(on_failure)]
+/// fn the_test(#[case] v: u32) {
+///     assert!(v > 0)
+/// }
+/// ```
+///
+/// If `case_2_big` fails it will print
+///
+/// ```bash
+/// - TEST ARGUMENTS the_test::case_2_big --
+/// v = 1000000
+/// ------------- TEST FAILED --------------
+/// ```
+///
+/// Set `RSTEST_TRACE_PRETTY` environment variable to pretty print (`{:#?}`) the arguments
+/// and `RSTEST_TRACE_MAX_LEN` to truncate the values longer than the given number of chars.
+/// If you enable the `tracing` (or `log`) feature of `rstest` the arguments are emitted as
+/// `tracing` events (or `log` records) with `rstest::trace` target instead of printed: one
+/// for each argument with `test`, `case`, `argument` and `failed` fields.
+///
+/// `#[trace(fixtures)]` traces also how the injected fixtures are built: each resolved
+/// fixture prints the method used to resolve it, its arguments and the returned value,
+/// indented by the nesting of the fixtures that depend on it.
+///
+/// ```
+/// # use rstest::*;
+/// #[fixture]
+/// fn base() -> u32 { 21 }
+///
+/// #[fixture]
+/// fn double(base: u32, #[default(2)] factor: u32) -> u32 { base * factor }
+///
+/// #[rstest]
+/// #[trace(fixtures)]
+/// fn the_test(double: u32) {
+///     assert_eq!(42, double)
+/// }
+/// ```
+///
+/// Will print
+///
+/// ```bash
+/// resolve my_crate::double::default
+///     resolve my_crate::base::default
+///     base() = 21
+/// double(base = 21, factor = 2) = 42
+/// ```
+///
+/// With the `tracing` or `log` feature each line is emitted with a `depth` field.
+///
 /// # Old _compact_ syntax
 ///
 /// `rstest` support also a syntax where all options and configuration can be write as
@@ -5,12 +5,6 @@ use syn::{
 
 pub(crate) struct Expressions(Vec<Expr>);
 
-impl Expressions {
-    pub(crate) fn take(self) -> Vec<Expr> {
-        self.0
-    }
-}
-
 impl Parse for Expressions {
     fn parse(input: ParseStream) -> Result<Self> {
         let values = input
@@ -9,7 +9,7 @@ use syn::{
 use super::{
     arguments::ArgumentsInfo,
     extract_argument_attrs, extract_default_return_type, extract_defaults, extract_fixtures,
-    extract_partials_return_type,
+    extract_partials_return_type, extract_patterns,
     future::{extract_futures, extract_global_awt},
     parse_vector_trailing_till_double_comma, Attributes, ExtendWithFunctionAttrs, Fixture,
 };
@@ -53,7 +53,9 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
         &mut self,
         item_fn: &mut ItemFn,
     ) -> std::result::Result<(), ErrorsVec> {
+        // Patterns should be replaced by idents before any other extraction
         let composed_tuple!(
+            patterns,
             fixtures,
             defaults,
             default_return_type,
@@ -62,6 +64,7 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
             futures,
             global_awt
         ) = merge_errors!(
+            extract_patterns(item_fn),
             extract_fixtures(item_fn),
             extract_defaults(item_fn),
             extract_default_return_type(item_fn),
@@ -85,6 +88,7 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
         self.arguments.set_once(once);
         self.arguments.set_global_await(global_awt);
         self.arguments.set_futures(futures.into_iter());
+        self.arguments.set_patterns(patterns.into_iter());
         Ok(())
     }
 }
@@ -16,10 +16,14 @@ use crate::{
 use fixture::{
     ArgumentValue, DefaultsFunctionExtractor, FixtureModifiers, FixturesFunctionExtractor,
 };
-use quote::ToTokens;
+use quote::{format_ident, ToTokens};
 use testcase::TestCase;
 
-use self::{expressions::Expressions, vlist::ValueList};
+use self::{
+    expressions::Expressions,
+    skip_if::SkipIf,
+    vlist::{ValueList, Values},
+};
 
 // To use the macros this should be the first one module
 #[macro_use]
@@ -28,9 +32,18 @@ pub(crate) mod macros;
 pub(crate) mod expressions;
 pub(crate) mod fixture;
 pub(crate) mod future;
+pub(crate) mod repeat;
+pub(crate) mod retry;
 pub(crate) mod rstest;
+pub(crate) mod runtimes;
+pub(crate) mod serial;
+pub(crate) mod skip_if;
+pub(crate) mod tag;
 pub(crate) mod testcase;
+pub(crate) mod timeout;
+pub(crate) mod trace;
 pub(crate) mod vlist;
+pub(crate) mod xfail;
 
 pub(crate) trait ExtendWithFunctionAttrs {
     fn extend_with_function_attrs(
@@ -391,6 +404,98 @@ impl VisitMut for IsOnceAttributeFunctionExtractor {
     }
 }
 
+/// Simple struct used to visit function arguments and replace every destructuring pattern
+/// by an ident: the one given by `#[name = "..."]` attribute or `arg_<position>`. Collect the
+/// replaced patterns and eventualy parsing errors
+#[derive(Default)]
+struct PatternsFunctionExtractor(Vec<(Ident, syn::Pat)>, Vec<syn::Error>);
+
+impl PatternsFunctionExtractor {
+    fn parse_name(attr: &syn::Attribute) -> syn::Result<Ident> {
+        match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(name),
+                        ..
+                    }),
+                ..
+            }) => name.parse(),
+            _ => Err(syn::Error::new_spanned(
+                attr,
+                r#"Use #[name = "..."] to give a name to the argument"#,
+            )),
+        }
+    }
+}
+
+impl VisitMut for PatternsFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        // The patterns names should not clash with the other arguments
+        let mut used = node
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|arg| match arg {
+                FnArg::Typed(arg) => match arg.pat.as_ref() {
+                    syn::Pat::Ident(pat) => Some(pat.ident.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for (pos, arg) in node.sig.inputs.iter_mut().enumerate() {
+            if let FnArg::Typed(arg) = arg {
+                let (names, attrs): (Vec<_>, Vec<_>) = std::mem::take(&mut arg.attrs)
+                    .into_iter()
+                    .partition(|a| attr_is(a, "name"));
+                arg.attrs = attrs;
+                if let syn::Pat::Ident(_) = arg.pat.as_ref() {
+                    self.1.extend(names.iter().map(|a| {
+                        syn::Error::new_spanned(
+                            a,
+                            "#[name] can be used just on destructuring patterns",
+                        )
+                    }));
+                    continue;
+                }
+                let name = match names.last().map(Self::parse_name).transpose() {
+                    Ok(name) => name.unwrap_or_else(|| format_ident!("arg_{}", pos + 1)),
+                    Err(err) => {
+                        self.1.push(err);
+                        continue;
+                    }
+                };
+                if used.contains(&name) {
+                    self.1.push(syn::Error::new_spanned(
+                        &arg.pat,
+                        format!(
+                            r#"Duplicate argument: '{name}' is already defined. Use #[name = "..."] to give this pattern a different name."#
+                        ),
+                    ));
+                    continue;
+                }
+                used.push(name.clone());
+                let pat = std::mem::replace(arg.pat.as_mut(), parse_quote! { #name });
+                self.0.push((name, pat));
+            }
+        }
+    }
+}
+
+/// Replace the destructuring patterns in function arguments by idents and return the
+/// original patterns. Should be called before any other extractor.
+pub(crate) fn extract_patterns(item_fn: &mut ItemFn) -> Result<Vec<(Ident, syn::Pat)>, ErrorsVec> {
+    let mut patterns_extractor = PatternsFunctionExtractor::default();
+    patterns_extractor.visit_item_fn_mut(item_fn);
+
+    if patterns_extractor.1.is_empty() {
+        Ok(patterns_extractor.0)
+    } else {
+        Err(patterns_extractor.1.into())
+    }
+}
+
 /// Simple struct used to visit function attributes and extract case arguments and
 /// eventualy parsing errors
 #[derive(Default)]
@@ -420,6 +525,39 @@ pub(crate) fn extract_case_args(item_fn: &mut ItemFn) -> Result<Vec<Ident>, Erro
     }
 }
 
+/// Simple struct used to visit function arguments and extract `#[skip_if]` conditions and
+/// eventualy parsing errors
+#[derive(Default)]
+struct SkipIfFunctionExtractor(Vec<(Ident, SkipIf)>, Vec<syn::Error>);
+
+impl VisitMut for SkipIfFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        for r in extract_argument_attrs(
+            node,
+            |a| attr_is(a, "skip_if"),
+            |a, name| a.parse_args().map(|skip_if| (name.clone(), skip_if)),
+        ) {
+            match r {
+                Ok(value) => self.0.push(value),
+                Err(err) => self.1.push(err),
+            }
+        }
+
+        syn::visit_mut::visit_fn_arg_mut(self, node);
+    }
+}
+
+pub(crate) fn extract_skip_if(item_fn: &mut ItemFn) -> Result<Vec<(Ident, SkipIf)>, ErrorsVec> {
+    let mut skip_if_extractor = SkipIfFunctionExtractor::default();
+    skip_if_extractor.visit_item_fn_mut(item_fn);
+
+    if skip_if_extractor.1.is_empty() {
+        Ok(skip_if_extractor.0)
+    } else {
+        Err(skip_if_extractor.1.into())
+    }
+}
+
 /// Simple struct used to visit function attributes and extract cases and
 /// eventualy parsing errors
 #[derive(Default)]
@@ -474,9 +612,9 @@ impl VisitMut for ValueListFunctionExtractor {
             node,
             |a| attr_is(a, "values"),
             |a, name| {
-                a.parse_args::<Expressions>().map(|v| ValueList {
+                a.parse_args::<Values>().map(|v| ValueList {
                     arg: name.clone(),
-                    values: v.take().into_iter().map(|e| e.into()).collect(),
+                    values: v.take(),
                 })
             },
         ) {
@@ -602,8 +740,16 @@ impl VisitMut for CheckTimeoutAttributesFunction {
             .collect::<Vec<_>>();
         let mut errors = timeouts
             .iter()
-            .map(|&attr| attr.parse_args::<syn::Expr>())
-            .filter_map(Result::err)
+            .filter_map(|&attr| match attr.parse_args::<timeout::Timeout>() {
+                Ok(timeout) if timeout.watchdog && node.sig.asyncness.is_some() => {
+                    Some(syn::Error::new_spanned(
+                        attr,
+                        "Watchdog timeout can be used just on sync tests",
+                    ))
+                }
+                Ok(_) => None,
+                Err(err) => Some(err),
+            })
             .collect::<Vec<_>>();
 
         if let Some(e) =
@@ -626,7 +772,9 @@ pub(crate) fn check_timeout_attrs(item_fn: &mut ItemFn) -> Result<(), ErrorsVec>
 pub(crate) mod arguments {
     use std::collections::HashMap;
 
-    use syn::Ident;
+    use syn::{Ident, Pat};
+
+    use super::skip_if::SkipIf;
 
     #[derive(PartialEq, Debug, Clone, Copy)]
     #[allow(dead_code)]
@@ -638,16 +786,19 @@ pub(crate) mod arguments {
         Await,
     }
 
-    
-
     #[derive(PartialEq, Default, Debug)]
     pub(crate) struct ArgumentInfo {
         future: FutureArg,
+        pattern: Option<Pat>,
+        skip_if: Vec<SkipIf>,
     }
 
     impl ArgumentInfo {
         fn future(future: FutureArg) -> Self {
-            Self { future }
+            Self {
+                future,
+                ..Default::default()
+            }
         }
 
         fn is_future(&self) -> bool {
@@ -682,6 +833,28 @@ pub(crate) mod arguments {
             futures.for_each(|(ident, k)| self.set_future(ident, k));
         }
 
+        pub(crate) fn set_patterns(&mut self, patterns: impl Iterator<Item = (Ident, Pat)>) {
+            patterns
+                .for_each(|(ident, pat)| self.args.entry(ident).or_default().pattern = Some(pat));
+        }
+
+        /// The destructuring pattern that was replaced by `ident` if any.
+        pub(crate) fn pattern(&self, ident: &Ident) -> Option<&Pat> {
+            self.args.get(ident).and_then(|arg| arg.pattern.as_ref())
+        }
+
+        pub(crate) fn add_skip_if(&mut self, ident: Ident, skip_if: SkipIf) {
+            self.args.entry(ident).or_default().skip_if.push(skip_if);
+        }
+
+        /// The `#[skip_if]` conditions that depend on the `ident` argument.
+        pub(crate) fn skip_if(&self, ident: &Ident) -> &[SkipIf] {
+            self.args
+                .get(ident)
+                .map(|arg| arg.skip_if.as_slice())
+                .unwrap_or_default()
+        }
+
         pub(crate) fn set_global_await(&mut self, is_global_await: bool) {
             self.is_global_await = is_global_await;
         }
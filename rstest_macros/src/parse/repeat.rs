@@ -0,0 +1,82 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, LitInt, Token,
+};
+
+/// Arguments of the `#[repeat(iterations, threads = n)]` attribute: `threads` is optional and
+/// runs the iterations concurrently on `n` threads.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Repeat {
+    pub(crate) iterations: u32,
+    pub(crate) threads: Option<u32>,
+}
+
+fn positive(input: ParseStream, what: &str) -> syn::Result<u32> {
+    let lit = input.parse::<LitInt>()?;
+    match lit.base10_parse()? {
+        0 => Err(syn::Error::new(
+            lit.span(),
+            format!("{what} should be at least 1"),
+        )),
+        value => Ok(value),
+    }
+}
+
+impl Parse for Repeat {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let iterations = positive(input, "Iterations")?;
+        let mut threads = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: Ident = input.parse()?;
+            if name != "threads" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Invalid repeat argument: use `threads`",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            threads = Some(positive(input, "Threads")?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self {
+            iterations,
+            threads,
+        })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::iterations("100", 100, None)]
+    #[case::trailing_comma("100,", 100, None)]
+    #[case::threads("100, threads = 4", 100, Some(4))]
+    #[case::threads_trailing_comma("100, threads = 4,", 100, Some(4))]
+    fn parse_repeat(#[case] input: &str, #[case] iterations: u32, #[case] threads: Option<u32>) {
+        let repeat: Repeat = input.ast();
+
+        assert_eq!(
+            Repeat {
+                iterations,
+                threads
+            },
+            repeat
+        );
+    }
+
+    #[rstest]
+    #[case::missed_iterations("", "expected integer literal")]
+    #[case::no_iterations("0", "Iterations should be at least 1")]
+    #[case::no_threads("10, threads = 0", "Threads should be at least 1")]
+    #[case::unknown_argument("10, parallel = 2", "use `threads`")]
+    #[case::threads_without_value("10, threads", "expected `=`")]
+    fn report_invalid_repeat(#[case] input: &str, #[case] message: &str) {
+        let error = syn::parse_str::<Repeat>(input).unwrap_err();
+
+        assert_in!(error.to_string(), message);
+    }
+}
@@ -0,0 +1,46 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    LitInt, Token,
+};
+
+/// Argument of the `#[retry(n)]` attribute: how many times a failing test runs again.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Retry {
+    pub(crate) retries: u32,
+}
+
+impl Parse for Retry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let retries = input.parse::<LitInt>()?.base10_parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { retries })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::retries("3", 3)]
+    #[case::trailing_comma("2,", 2)]
+    #[case::no_retries("0", 0)]
+    fn parse_retry(#[case] input: &str, #[case] retries: u32) {
+        let retry: Retry = input.ast();
+
+        assert_eq!(Retry { retries }, retry);
+    }
+
+    #[rstest]
+    #[case::missed_retries("", "expected integer literal")]
+    #[case::negative("-1", "invalid digit")]
+    #[case::not_an_integer("times", "expected integer literal")]
+    #[case::too_big("5000000000", "number too large")]
+    fn report_invalid_retry(#[case] input: &str, #[case] message: &str) {
+        let error = syn::parse_str::<Retry>(input).unwrap_err();
+
+        assert_in!(error.to_string(), message);
+    }
+}
@@ -8,7 +8,7 @@ use self::files::{extract_files, ValueListFromFiles};
 use super::{
     arguments::ArgumentsInfo,
     check_timeout_attrs, extract_case_args, extract_cases, extract_excluded_trace,
-    extract_fixtures, extract_value_list,
+    extract_fixtures, extract_patterns, extract_skip_if, extract_value_list,
     future::{extract_futures, extract_global_awt},
     parse_vector_trailing_till_double_comma,
     testcase::TestCase,
@@ -18,6 +18,7 @@ use crate::parse::vlist::ValueList;
 use crate::{
     error::ErrorsVec,
     refident::{MaybeIdent, RefIdent},
+    utils::fn_args_has_ident,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, ToTokens};
@@ -48,18 +49,50 @@ impl Parse for RsTestInfo {
     }
 }
 
+impl RsTestInfo {
+    /// Options can be given as plain idents in `rstest` attribute (i.e. `#[rstest(stable_names)]`):
+    /// they are parsed as case arguments, so move them to attributes. An ident that is also a
+    /// function argument remains a case argument.
+    fn extract_options(&mut self, item_fn: &ItemFn) {
+        let (options, items): (Vec<_>, Vec<_>) = std::mem::take(&mut self.data.items)
+            .into_iter()
+            .partition(|it| match it {
+                RsTestItem::CaseArgName(ident) => {
+                    RsTestAttributes::is_option(ident) && !fn_args_has_ident(item_fn, ident)
+                }
+                _ => false,
+            });
+        self.data.items = items;
+        options
+            .into_iter()
+            .filter_map(|it| match it {
+                RsTestItem::CaseArgName(ident) => Some(ident),
+                _ => None,
+            })
+            .for_each(|ident| self.attributes.append(Attribute::Attr(ident)));
+    }
+}
+
 impl ExtendWithFunctionAttrs for RsTestInfo {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let composed_tuple!(_inner, excluded, _timeout, futures, global_awt) = merge_errors!(
+        self.extract_options(item_fn);
+        // Patterns should be replaced by idents before any other extraction
+        let composed_tuple!(patterns, _inner, excluded, _timeout, futures, global_awt, skip_if) = merge_errors!(
+            extract_patterns(item_fn),
             self.data.extend_with_function_attrs(item_fn),
             extract_excluded_trace(item_fn),
             check_timeout_attrs(item_fn),
             extract_futures(item_fn),
-            extract_global_awt(item_fn)
+            extract_global_awt(item_fn),
+            extract_skip_if(item_fn)
         )?;
         self.attributes.add_notraces(excluded);
         self.arguments.set_global_await(global_awt);
         self.arguments.set_futures(futures.into_iter());
+        self.arguments.set_patterns(patterns.into_iter());
+        skip_if
+            .into_iter()
+            .for_each(|(ident, skip_if)| self.arguments.add_skip_if(ident, skip_if));
         Ok(())
     }
 }
@@ -115,6 +148,31 @@ impl RsTestData {
     pub(crate) fn has_list_values(&self) -> bool {
         self.list_values().next().is_some()
     }
+
+    /// The attributes given to the cases and to the values.
+    pub(crate) fn tests_attrs(&self) -> impl Iterator<Item = &syn::Attribute> {
+        self.cases().flat_map(|case| case.attrs.iter()).chain(
+            self.list_values()
+                .flat_map(|vlist| vlist.values.iter())
+                .flat_map(|value| value.attrs.iter()),
+        )
+    }
+
+    fn resolve_named_case_args(&mut self) -> Result<(), ErrorsVec> {
+        let case_args = self.case_args().cloned().collect::<Vec<_>>();
+        let mut errors = ErrorsVec::default();
+        for item in self.items.iter_mut() {
+            if let RsTestItem::TestCase(case) = item {
+                if let Err(mut e) = case.resolve_named_args(&case_args) {
+                    errors.append(&mut e);
+                }
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
 }
 
 impl Parse for RsTestData {
@@ -149,7 +207,7 @@ impl ExtendWithFunctionAttrs for RsTestData {
                 .into_iter()
                 .map(|f| f.into()),
         );
-        Ok(())
+        self.resolve_named_case_args()
     }
 }
 
@@ -230,6 +288,30 @@ wrap_attributes!(RsTestAttributes);
 impl RsTestAttributes {
     const TRACE_VARIABLE_ATTR: &'static str = "trace";
     const NOTRACE_VARIABLE_ATTR: &'static str = "notrace";
+    const STABLE_NAMES_ATTR: &'static str = "stable_names";
+    const FLAT_ATTR: &'static str = "flat";
+    const BENCH_ATTR: &'static str = "bench";
+    const OPTIONS: &'static [&'static str] =
+        &[Self::STABLE_NAMES_ATTR, Self::FLAT_ATTR, Self::BENCH_ATTR];
+
+    pub(crate) fn is_option(ident: &Ident) -> bool {
+        Self::OPTIONS.iter().any(|&o| ident == o)
+    }
+
+    pub(crate) fn stable_names(&self) -> bool {
+        self.iter()
+            .any(|m| matches!(m, Attribute::Attr(i) if i == Self::STABLE_NAMES_ATTR))
+    }
+
+    pub(crate) fn flat(&self) -> bool {
+        self.iter()
+            .any(|m| matches!(m, Attribute::Attr(i) if i == Self::FLAT_ATTR))
+    }
+
+    pub(crate) fn bench(&self) -> bool {
+        self.iter()
+            .any(|m| matches!(m, Attribute::Attr(i) if i == Self::BENCH_ATTR))
+    }
 
     pub(crate) fn trace_me(&self, ident: &Ident) -> bool {
         if self.should_trace() {
@@ -321,6 +403,22 @@ mod test {
         assert_eq!(2, errors.len());
     }
 
+    #[test]
+    fn should_return_error_for_async_watchdog_timeout() {
+        let mut item_fn = r#"
+            #[timeout(Duration::from_millis(20), watchdog)]
+            async fn test_fn(#[case] arg: u32) {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert!(format!("{:?}", errors).contains("just on sync tests"))
+    }
+
     #[cfg(feature = "async-timeout")]
     #[test]
     fn should_parse_async_timeout() {
@@ -526,6 +624,73 @@ mod test {
             assert_eq!(attrs("#[something_else]"), b_args);
         }
 
+        #[test]
+        fn replace_patterns_by_idents() {
+            let mut item_fn = r#"
+            fn test_fn(#[case] (a, b): (u32, u32), #[values(Some(1))] #[name = "opt"] Some(c): Option<u32>, d: u32) {
+            }
+            "#
+            .ast();
+            let expected: ItemFn =
+                "fn test_fn(arg_1: (u32, u32), opt: Option<u32>, d: u32) {}".ast();
+
+            let mut info = RsTestInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(expected, item_fn);
+            assert_eq!(
+                vec!["arg_1"],
+                info.data
+                    .case_args()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                "opt",
+                info.data.list_values().next().unwrap().arg.to_string()
+            );
+            assert_eq!(
+                "(a , b)",
+                info.arguments.pattern(&ident("arg_1")).display_code()
+            );
+            assert_eq!(
+                "Some (c)",
+                info.arguments.pattern(&ident("opt")).display_code()
+            );
+            assert!(info.arguments.pattern(&ident("d")).is_none());
+        }
+
+        #[rstest]
+        #[case::on_ident(
+            r#"fn f(#[name = "x"] a: u32) {}"#,
+            "can be used just on destructuring patterns"
+        )]
+        #[case::not_a_string(r#"fn f(#[name(x)] (a, b): (u32, u32)) {}"#, r#"Use #[name = "..."]"#)]
+        #[case::not_an_ident(r#"fn f(#[name = "not valid"] (a, b): (u32, u32)) {}"#, "")]
+        #[case::default_name_clash(
+            "fn f((a, b): (u32, u32), arg_1: u32) {}",
+            r#"Duplicate argument: 'arg_1' is already defined. Use #[name = "..."]"#
+        )]
+        #[case::name_clash(
+            r#"fn f(c: u32, #[name = "c"] (a, b): (u32, u32)) {}"#,
+            "Duplicate argument: 'c' is already defined."
+        )]
+        #[case::patterns_names_clash(
+            r#"fn f((a, b): (u32, u32), #[name = "arg_1"] Some(c): Option<u32>) {}"#,
+            "Duplicate argument: 'arg_1' is already defined."
+        )]
+        fn report_invalid_name(#[case] item_fn: &str, #[case] message: &str) {
+            let mut item_fn = item_fn.ast();
+
+            let errors = RsTestInfo::default()
+                .extend_with_function_attrs(&mut item_fn)
+                .unwrap_err();
+
+            assert_eq!(1, errors.len());
+            rstest_test::assert_in!(errors[0].to_string(), message);
+        }
+
         #[rstest]
         fn extract_future() {
             let mut item_fn = "fn f(#[future] a: u32, b: u32) {}".ast();
@@ -539,6 +704,41 @@ mod test {
             assert!(info.arguments.is_future(&ident("a")));
             assert!(!info.arguments.is_future(&ident("b")));
         }
+
+        #[test]
+        fn extract_skip_if() {
+            let mut item_fn = r#"
+            fn f(#[skip_if(a > 1)] #[skip_if(a == 0, reason = "zero")] a: u32, b: u32) {}
+            "#
+            .ast();
+            let expected = "fn f(a: u32, b: u32) {}".ast();
+
+            let mut info = RsTestInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(item_fn, expected);
+            assert_eq!(
+                vec!["a > 1", "a == 0"],
+                info.arguments
+                    .skip_if(&ident("a"))
+                    .iter()
+                    .map(|s| s.condition.display_code())
+                    .collect::<Vec<_>>()
+            );
+            assert!(info.arguments.skip_if(&ident("b")).is_empty());
+        }
+
+        #[test]
+        fn report_invalid_skip_if() {
+            let mut item_fn = "fn f(#[skip_if(a > 1, because = 42)] a: u32) {}".ast();
+
+            let errors = RsTestInfo::default()
+                .extend_with_function_attrs(&mut item_fn)
+                .unwrap_err();
+
+            assert_eq!(1, errors.len());
+        }
     }
 
     mod parametrize_cases {
@@ -616,6 +816,48 @@ mod test {
                 );
             }
 
+            #[test]
+            fn named_arguments() {
+                let mut item_fn = r#"
+                #[case(arg2 = "first", arg1 = 42)]
+                #[case(43, arg2 = "second")]
+                fn test_fn(#[case] arg1: u32, #[case] arg2: &str) {
+                }
+                "#
+                .ast();
+
+                let mut info = RsTestInfo::default();
+
+                info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+                let cases = info.data.cases().cloned().collect::<Vec<_>>();
+
+                assert_eq!(
+                    vec![
+                        TestCase::from_iter(["42", r#""first""#].iter()),
+                        TestCase::from_iter(["43", r#""second""#].iter()),
+                    ],
+                    cases
+                );
+            }
+
+            #[test]
+            fn report_named_arguments_errors() {
+                let mut item_fn = r#"
+                #[case(arg1 = 42)]
+                #[case(arg3 = 43, arg1 = 42, arg2 = "")]
+                fn test_fn(#[case] arg1: u32, #[case] arg2: &str) {
+                }
+                "#
+                .ast();
+
+                let mut info = RsTestInfo::default();
+
+                let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_eq!(2, errors.len());
+            }
+
             #[test]
             fn parse_tuple_value() {
                 let mut item_fn = r#"
@@ -781,6 +1023,18 @@ mod test {
             );
         }
 
+        #[test]
+        fn named_arguments_in_compact_syntax() {
+            let mut item_fn = "fn test_fn(a: u32, b: u32) {}".ast();
+            let mut info = parse_rstest("a, b, case(b = 2, a = 1)");
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            let cases = info.data.cases().collect::<Vec<_>>();
+
+            assert_eq!(to_args!(["1", "2"]), cases[0].args());
+        }
+
         #[test]
         fn case_could_be_arg_name() {
             let data = parse_rstest(
@@ -798,6 +1052,61 @@ mod test {
             assert_eq!(1, cases.len());
             assert_eq!(to_args!(["42"]), cases[0].args());
         }
+
+        #[test]
+        fn stable_names_option() {
+            let mut item_fn = "fn test_fn(#[case] arg: u32) {}".ast();
+            let mut info = parse_rstest("stable_names");
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.stable_names());
+            assert_eq!(
+                vec!["arg"],
+                info.data
+                    .case_args()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn stable_names_could_be_arg_name() {
+            let mut item_fn = "fn test_fn(stable_names: u32) {}".ast();
+            let mut info = parse_rstest("stable_names, case(42)");
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(!info.attributes.stable_names());
+            assert_eq!(
+                "stable_names",
+                &info.data.case_args().next().unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn flat_option() {
+            let mut item_fn = "fn test_fn(#[values(1, 2)] arg: u32) {}".ast();
+            let mut info = parse_rstest("flat, stable_names");
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.flat());
+            assert!(info.attributes.stable_names());
+            assert!(!info.data.has_case_args());
+        }
+
+        #[test]
+        fn bench_option() {
+            let mut item_fn = "fn test_fn(#[case] arg: u32) {}".ast();
+            let mut info = parse_rstest("bench");
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.bench());
+            assert!(!info.attributes.flat());
+            assert_eq!(1, info.data.case_args().count());
+        }
     }
 
     mod matrix_cases {
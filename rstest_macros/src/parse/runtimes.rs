@@ -0,0 +1,80 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, Token,
+};
+
+use crate::render::runtime::Runtime;
+
+/// Arguments of the `#[runtimes(tokio, async_std, smol)]` attribute.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Runtimes(Vec<Runtime>);
+
+impl Runtimes {
+    pub(crate) fn take(self) -> Vec<Runtime> {
+        self.0
+    }
+}
+
+impl Parse for Runtimes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let names = Punctuated::<Ident, Token![,]>::parse_terminated(input)?;
+        if names.is_empty() {
+            return Err(input.error("Runtimes list should not be empty"));
+        }
+        let mut runtimes = Vec::new();
+        for name in names {
+            let runtime = Runtime::from_name(&name.to_string()).ok_or_else(|| {
+                syn::Error::new(
+                    name.span(),
+                    format!(
+                        "Unknown runtime '{name}': use one of {}",
+                        Runtime::NAMES.join(", ")
+                    ),
+                )
+            })?;
+            if runtimes.contains(&runtime) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Duplicate runtime '{name}'"),
+                ));
+            }
+            runtimes.push(runtime);
+        }
+        Ok(Self(runtimes))
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    #[test]
+    fn parse_runtimes_in_the_given_order() {
+        let runtimes: Runtimes = "tokio, async_std, smol, tokio_multi_thread,".ast();
+
+        assert_eq!(
+            vec![
+                Runtime::Tokio {
+                    multi_thread: false
+                },
+                Runtime::AsyncStd,
+                Runtime::Smol,
+                Runtime::Tokio { multi_thread: true },
+            ],
+            runtimes.take()
+        );
+    }
+
+    #[rstest]
+    #[case::empty("", "should not be empty")]
+    #[case::unknown("tokio, actix", "Unknown runtime 'actix'")]
+    #[case::duplicate("smol, tokio, smol", "Duplicate runtime 'smol'")]
+    #[case::not_an_ident(r#""tokio""#, "expected identifier")]
+    fn return_error(#[case] code: &str, #[case] message: &str) {
+        let error = syn::parse_str::<Runtimes>(code).unwrap_err();
+
+        rstest_test::assert_in!(error.to_string(), message);
+    }
+}
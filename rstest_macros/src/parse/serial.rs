@@ -0,0 +1,51 @@
+use syn::{punctuated::Punctuated, Attribute, Ident, Meta, Token};
+
+/// The keys of the `#[serial(db, port)]` and `#[parallel(db, port)]` attributes: the attributes
+/// without arguments use the default (empty) key.
+#[derive(Debug, PartialEq, Default, Clone)]
+pub(crate) struct Keys(pub(crate) Vec<Ident>);
+
+impl Keys {
+    pub(crate) fn from_attr(attr: &Attribute) -> syn::Result<Self> {
+        if let Meta::Path(_) = attr.meta {
+            return Ok(Self::default());
+        }
+        let keys = attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?;
+        Ok(Self(keys.into_iter().collect()))
+    }
+
+    /// The keys names: the default key is the empty one.
+    pub(crate) fn names(&self) -> Vec<String> {
+        match self.0.as_slice() {
+            [] => vec![String::new()],
+            keys => keys.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn keys(code: &str) -> syn::Result<Keys> {
+        Keys::from_attr(&attrs(code)[0])
+    }
+
+    #[rstest]
+    #[case::no_args("#[serial]", &[""])]
+    #[case::empty("#[serial()]", &[""])]
+    #[case::one("#[parallel(db)]", &["db"])]
+    #[case::more("#[serial(db, port,)]", &["db", "port"])]
+    fn parse(#[case] code: &str, #[case] expected: &[&str]) {
+        assert_eq!(expected, keys(code).unwrap().names());
+    }
+
+    #[rstest]
+    #[case::not_an_ident(r#"#[serial("db")]"#)]
+    #[case::name_value(r#"#[serial = "db"]"#)]
+    #[case::expression("#[parallel(db = 1)]")]
+    fn return_error(#[case] code: &str) {
+        assert!(keys(code).is_err());
+    }
+}
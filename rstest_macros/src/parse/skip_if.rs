@@ -0,0 +1,94 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    Expr, Ident, LitStr, Token,
+};
+
+/// Arguments of the `#[skip_if(condition, reason = "...")]` attribute: `reason` is optional.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct SkipIf {
+    pub(crate) condition: Expr,
+    pub(crate) reason: Option<LitStr>,
+}
+
+impl Parse for SkipIf {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let condition = input.parse()?;
+        let mut reason = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: Ident = input.parse()?;
+            if name != "reason" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Invalid skip_if argument: use `reason`",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            reason = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { condition, reason })
+    }
+}
+
+impl SkipIf {
+    /// Render the check that returns `passed` if the test should be skipped.
+    pub(crate) fn render(&self, passed: &TokenStream) -> TokenStream {
+        let condition = &self.condition;
+        let reason = self
+            .reason
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_else(|| condition.to_token_stream().to_string());
+        quote! {
+            if #condition {
+                rstest::skip::report(#reason);
+                return #passed;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::just_condition("cfg!(windows)", "cfg!(windows)", None)]
+    #[case::trailing_comma("cfg!(windows),", "cfg!(windows)", None)]
+    #[case::reason(
+        r#"std::env::var("DB").is_err(), reason = "no db""#,
+        r#"std::env::var("DB").is_err()"#,
+        Some("no db")
+    )]
+    fn parse(#[case] code: &str, #[case] condition: &str, #[case] reason: Option<&str>) {
+        let skip_if: SkipIf = code.ast();
+
+        assert_eq!(expr(condition), skip_if.condition);
+        assert_eq!(
+            reason.map(ToOwned::to_owned),
+            skip_if.reason.map(|r| r.value())
+        );
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::unknown_arg(r#"true, because = "bug""#)]
+    #[case::reason_not_a_string("true, reason = 42")]
+    fn return_error(#[case] code: &str) {
+        assert!(syn::parse_str::<SkipIf>(code).is_err());
+    }
+
+    #[test]
+    fn use_condition_as_default_reason() {
+        let skip_if: SkipIf = "a == 42".ast();
+
+        assert_in!(
+            skip_if.render(&quote! { () }).display_code(),
+            r#"report ("a == 42")"#
+        );
+    }
+}
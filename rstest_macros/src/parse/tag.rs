@@ -0,0 +1,46 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, Token,
+};
+
+/// Arguments of the `#[tag(slow, db)]` attribute: a not empty list of tag names.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Tags(pub(crate) Vec<Ident>);
+
+impl Parse for Tags {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let tags = Punctuated::<Ident, Token![,]>::parse_terminated(input)?;
+        if tags.is_empty() {
+            return Err(input.error("Missed tags: use #[tag(name, ...)]"));
+        }
+        Ok(Self(tags.into_iter().collect()))
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::one("slow", &["slow"])]
+    #[case::more("slow, db", &["slow", "db"])]
+    #[case::trailing_comma("slow, db,", &["slow", "db"])]
+    fn parse_tags(#[case] input: &str, #[case] expected: &[&str]) {
+        let tags: Tags = input.ast();
+
+        assert_eq!(to_idents!(expected), tags.0);
+    }
+
+    #[rstest]
+    #[case::empty("", "Missed tags")]
+    #[case::not_an_ident(r#""slow""#, "expected identifier")]
+    #[case::expression("slow = true", "expected `,`")]
+    fn report_invalid_tags(#[case] input: &str, #[case] message: &str) {
+        let error = syn::parse_str::<Tags>(input).unwrap_err();
+
+        assert_in!(error.to_string(), message);
+    }
+}
@@ -7,6 +7,11 @@ use syn::{
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
+use crate::{
+    error::ErrorsVec,
+    utils::{sanitize_ident, stable_hash, tokens_to_string},
+};
+
 #[derive(PartialEq, Debug, Clone)]
 /// A test case instance data. Contains a list of arguments. It is parsed by parametrize
 /// attributes.
@@ -42,6 +47,122 @@ impl Parse for TestCase {
     }
 }
 
+/// Return name and value if the given case argument is in the `name = value` form and `name`
+/// is one of the `case_args`: any other assignment is a positional value.
+fn named_arg<'a>(arg: &'a Expr, case_args: &[Ident]) -> Option<(&'a Ident, &'a Expr)> {
+    match arg {
+        Expr::Assign(assign) if assign.attrs.is_empty() => match assign.left.as_ref() {
+            Expr::Path(p) if p.attrs.is_empty() && p.qself.is_none() => p
+                .path
+                .get_ident()
+                .filter(|name| case_args.contains(name))
+                .map(|name| (name, assign.right.as_ref())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl TestCase {
+    const STABLE_NAME_MAX_LEN: usize = 64;
+    const STABLE_NAME_PREFIX_LEN: usize = 48;
+
+    /// Case name that depends only on the case itself and not on its position: use the
+    /// description if any, otherwise the sanitized arguments. Arguments that are too long or
+    /// that don't leave anything after sanitization are shortened by an hash of their tokens:
+    /// the tokens are printed by `tokens_to_string()` to not depend on the compiler version.
+    pub(crate) fn stable_name(&self) -> String {
+        if let Some(description) = &self.description {
+            return format!("case_{description}");
+        }
+        let args = self
+            .args
+            .iter()
+            .map(|a| tokens_to_string(a.to_token_stream()))
+            .collect::<Vec<_>>();
+        let sanitized = args
+            .iter()
+            .map(|a| sanitize_ident(a))
+            .collect::<Vec<_>>()
+            .join("_");
+        let hash = stable_hash(&args.join(","));
+        // Identifiers can contain non ASCII chars: measure and cut chars and not bytes
+        match sanitized.chars().count() {
+            0 => format!("case_{hash:08x}"),
+            len if len <= Self::STABLE_NAME_MAX_LEN => format!("case_{sanitized}"),
+            _ => format!(
+                "case_{}_{hash:08x}",
+                sanitized
+                    .chars()
+                    .take(Self::STABLE_NAME_PREFIX_LEN)
+                    .collect::<String>()
+            ),
+        }
+    }
+
+    pub(crate) fn has_named_args(&self, case_args: &[Ident]) -> bool {
+        self.args.iter().any(|a| named_arg(a, case_args).is_some())
+    }
+
+    /// Reorder arguments given in the `name = value` form by following `case_args` order. Like
+    /// in a function call, positional arguments should precede the named ones. If something
+    /// goes wrong the case is left untouched.
+    pub(crate) fn resolve_named_args(
+        &mut self,
+        case_args: &[Ident],
+    ) -> std::result::Result<(), ErrorsVec> {
+        if !self.has_named_args(case_args) {
+            return Ok(());
+        }
+        let mut errors = Vec::new();
+        let mut values: Vec<Option<Expr>> = vec![None; case_args.len()];
+        let mut named = false;
+        for (pos, arg) in self.args.iter().enumerate() {
+            match named_arg(arg, case_args) {
+                Some((name, value)) => {
+                    named = true;
+                    // `named_arg()` returns just the case arguments
+                    let index = case_args.iter().position(|a| a == name).unwrap();
+                    match values[index] {
+                        Some(_) => errors.push(Error::new_spanned(
+                            name,
+                            format!("Duplicate argument: '{name}' is already defined."),
+                        )),
+                        None => values[index] = Some(value.clone()),
+                    }
+                }
+                None if named => errors.push(Error::new_spanned(
+                    arg,
+                    "Positional argument cannot follow named arguments.",
+                )),
+                None if pos >= case_args.len() => errors.push(Error::new_spanned(
+                    arg,
+                    "Wrong case signature: should match the given parameters list.",
+                )),
+                None => values[pos] = Some(arg.clone()),
+            }
+        }
+        let missed = case_args
+            .iter()
+            .zip(values.iter())
+            .filter(|(_, v)| v.is_none())
+            .map(|(a, _)| format!("'{a}'"))
+            .collect::<Vec<_>>();
+        if !missed.is_empty() {
+            errors.push(Error::new_spanned(
+                &*self,
+                format!("Missed case arguments: {}.", missed.join(", ")),
+            ));
+        }
+        if errors.is_empty() {
+            self.args = values.into_iter().flatten().collect();
+            Ok(())
+        } else {
+            Err(errors.into())
+        }
+    }
+}
+
 impl ToTokens for TestCase {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.args.iter().for_each(|c| c.to_tokens(tokens))
@@ -102,6 +223,18 @@ mod should {
         assert_eq!(to_args!(["42"]), args);
     }
 
+    #[rstest]
+    #[case::fit_in_chars("é".repeat(60), format!("case___{}__", "é".repeat(60)))]
+    #[case::truncate_chars(
+        "é".repeat(100),
+        format!("case___{}_{:08x}", "é".repeat(46), stable_hash(&format!("{:?}", "é".repeat(100))))
+    )]
+    fn stable_name_should_count_chars_and_not_bytes(#[case] arg: String, #[case] expected: String) {
+        let test_case = TestCase::from_iter([format!("{arg:?}").as_str()]);
+
+        assert_eq!(expected, test_case.stable_name());
+    }
+
     #[test]
     fn get_description_also_with_more_args() {
         let test_case = parse_test_case(r#"case :: this_test_description (42, 24)"#);
@@ -159,4 +292,91 @@ mod should {
         assert!(content.contains("should_panic"));
         assert!(content.contains("other_attr"));
     }
+
+    mod resolve_named_args {
+        use super::{assert_eq, *};
+        use rstest_test::assert_in;
+
+        fn resolve(case: &str, case_args: &[&str]) -> std::result::Result<TestCase, String> {
+            let mut test_case = parse_test_case(case);
+            test_case
+                .resolve_named_args(&to_idents!(case_args))
+                .map(|_| test_case)
+                .map_err(|errors| {
+                    errors
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+        }
+
+        #[rstest]
+        #[case::just_positional("case(1, 2, 3)")]
+        #[case::just_named("case(a = 1, b = 2, c = 3)")]
+        #[case::named_in_any_order("case(c = 3, a = 1, b = 2)")]
+        #[case::mixed("case(1, c = 3, b = 2)")]
+        fn should_reorder_arguments(#[case] case: &str) {
+            let test_case = resolve(case, &["a", "b", "c"]).unwrap();
+
+            assert_eq!(to_args!(["1", "2", "3"]), test_case.args());
+        }
+
+        #[test]
+        fn should_not_touch_positional_assignments_to_complex_expressions() {
+            let test_case = resolve("case(a.b = 1, *c = 2)", &["a", "c"]).unwrap();
+
+            assert_eq!(to_args!(["a.b = 1", "*c = 2"]), test_case.args());
+        }
+
+        #[test]
+        fn should_not_touch_positional_assignments_to_other_names() {
+            let test_case = resolve("case(x = 1, c = 3, b = 2)", &["a", "b", "c"]).unwrap();
+
+            assert_eq!(to_args!(["x = 1", "2", "3"]), test_case.args());
+            assert_eq!(
+                to_args!(["x = 1"]),
+                resolve("case(x = 1)", &["a"]).unwrap().args()
+            );
+        }
+
+        #[rstest]
+        #[case::missed("case(a = 1)", "Missed case arguments: 'b', 'c'.")]
+        #[case::other_name_after_named(
+            "case(a = 1, b = 2, c = 3, d = 4)",
+            "Positional argument cannot follow named arguments."
+        )]
+        #[case::duplicate(
+            "case(a = 1, b = 2, c = 3, a = 4)",
+            "Duplicate argument: 'a' is already defined."
+        )]
+        #[case::duplicate_positional(
+            "case(1, b = 2, c = 3, a = 4)",
+            "Duplicate argument: 'a' is already defined."
+        )]
+        #[case::positional_after_named(
+            "case(a = 1, 2, c = 3)",
+            "Positional argument cannot follow named arguments."
+        )]
+        #[case::too_many_positional(
+            "case(1, 2, 3, 4, c = 3)",
+            "Wrong case signature: should match the given parameters list."
+        )]
+        fn should_return_error(#[case] case: &str, #[case] message: &str) {
+            let errors = resolve(case, &["a", "b", "c"]).unwrap_err();
+
+            assert_in!(errors, message);
+        }
+
+        #[test]
+        fn should_leave_case_untouched_on_errors() {
+            let mut test_case = parse_test_case("case(b = 1)");
+            let expected = test_case.clone();
+
+            assert!(test_case
+                .resolve_named_args(&to_idents!(["a", "b"]))
+                .is_err());
+            assert_eq!(expected, test_case);
+        }
+    }
 }
@@ -0,0 +1,64 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    Expr, Ident, Token,
+};
+
+/// Arguments of the `#[timeout(duration, watchdog)]` attribute: `watchdog` is optional and
+/// runs the test on the current thread under a watchdog.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Timeout {
+    pub(crate) duration: Expr,
+    pub(crate) watchdog: bool,
+}
+
+impl Parse for Timeout {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let duration = input.parse()?;
+        let mut watchdog = false;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: Ident = input.parse()?;
+            if name != "watchdog" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Invalid timeout argument: use `watchdog`",
+                ));
+            }
+            watchdog = true;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { duration, watchdog })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::just_duration("Duration::from_millis(10)", false)]
+    #[case::trailing_comma("Duration::from_millis(10),", false)]
+    #[case::watchdog("Duration::from_millis(10), watchdog", true)]
+    #[case::watchdog_trailing_comma("Duration::from_millis(10), watchdog,", true)]
+    fn parse_timeout(#[case] input: &str, #[case] watchdog: bool) {
+        let timeout: Timeout = input.ast();
+
+        assert_eq!(
+            Timeout {
+                duration: "Duration::from_millis(10)".ast(),
+                watchdog
+            },
+            timeout
+        );
+    }
+
+    #[rstest]
+    #[case::unknown_argument("ms(10), thread", "use `watchdog`")]
+    #[case::missed_duration("", "expected an expression")]
+    fn report_invalid_timeout(#[case] input: &str, #[case] message: &str) {
+        let error = syn::parse_str::<Timeout>(input).unwrap_err();
+
+        assert_in!(error.to_string(), message);
+    }
+}
@@ -0,0 +1,59 @@
+use syn::{Attribute, Meta};
+
+/// Arguments of the `#[trace]` attribute: `#[trace(on_failure)]` prints the test arguments
+/// only if the test fails and `#[trace(fixtures)]` traces also the fixtures construction.
+#[derive(Debug, PartialEq, Default, Clone)]
+pub(crate) struct Trace {
+    pub(crate) on_failure: bool,
+    pub(crate) fixtures: bool,
+}
+
+impl Trace {
+    pub(crate) fn from_attr(attr: &Attribute) -> syn::Result<Self> {
+        let mut trace = Self::default();
+        if let Meta::Path(_) = attr.meta {
+            return Ok(trace);
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("on_failure") {
+                trace.on_failure = true;
+            } else if meta.path.is_ident("fixtures") {
+                trace.fixtures = true;
+            } else {
+                return Err(meta.error("Invalid trace argument: use `on_failure` or `fixtures`"));
+            }
+            Ok(())
+        })?;
+        Ok(trace)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn trace(code: &str) -> syn::Result<Trace> {
+        Trace::from_attr(&attrs(code)[0])
+    }
+
+    #[rstest]
+    #[case::no_args("#[trace]", false, false)]
+    #[case::empty("#[trace()]", false, false)]
+    #[case::on_failure("#[trace(on_failure)]", true, false)]
+    #[case::fixtures("#[trace(fixtures)]", false, true)]
+    #[case::both("#[trace(fixtures, on_failure)]", true, true)]
+    fn parse(#[case] code: &str, #[case] on_failure: bool, #[case] fixtures: bool) {
+        let trace = trace(code).unwrap();
+
+        assert_eq!(on_failure, trace.on_failure);
+        assert_eq!(fixtures, trace.fixtures);
+    }
+
+    #[rstest]
+    #[case::unknown_arg("#[trace(always)]")]
+    #[case::name_value(r#"#[trace = "on_failure"]"#)]
+    fn return_error(#[case] code: &str) {
+        assert!(trace(code).is_err());
+    }
+}
@@ -2,22 +2,25 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
     parse::{Parse, ParseStream, Result},
-    Expr, Ident, Token,
+    Attribute, Expr, Ident, Token,
 };
 
 use crate::refident::RefIdent;
 
-use super::expressions::Expressions;
-
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct Value {
     pub(crate) expr: Expr,
     pub(crate) description: Option<String>,
+    pub(crate) attrs: Vec<Attribute>,
 }
 
 impl Value {
     pub(crate) fn new(expr: Expr, description: Option<String>) -> Self {
-        Self { expr, description }
+        Self {
+            expr,
+            description,
+            attrs: Default::default(),
+        }
     }
 
     pub(crate) fn description(&self) -> String {
@@ -33,6 +36,43 @@ impl From<Expr> for Value {
     }
 }
 
+/// A value can have its own attributes and a name: `#[should_panic] overflow => u32::MAX`.
+impl Parse for Value {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let description = if input.peek(Ident) && input.peek2(Token![=>]) {
+            let name: Ident = input.parse()?;
+            let _to: Token![=>] = input.parse()?;
+            Some(name.to_string())
+        } else {
+            None
+        };
+        Ok(Self {
+            expr: input.parse()?,
+            description,
+            attrs,
+        })
+    }
+}
+
+pub(crate) struct Values(Vec<Value>);
+
+impl Values {
+    pub(crate) fn take(self) -> Vec<Value> {
+        self.0
+    }
+}
+
+impl Parse for Values {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let values = input
+            .parse_terminated(Parse::parse, Token![,])?
+            .into_iter()
+            .collect();
+        Ok(Self(values))
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct ValueList {
     pub(crate) arg: Ident,
@@ -45,11 +85,11 @@ impl Parse for ValueList {
         let _to: Token![=>] = input.parse()?;
         let content;
         let paren = syn::bracketed!(content in input);
-        let values: Expressions = content.parse()?;
+        let values: Values = content.parse()?;
 
         let ret = Self {
             arg,
-            values: values.take().into_iter().map(|e| e.into()).collect(),
+            values: values.take(),
         };
         if ret.values.is_empty() {
             Err(syn::Error::new(
@@ -120,6 +160,55 @@ mod should {
             parse_values_list(r#"other => [some:<>(1,2,3)]"#);
         }
 
+        #[test]
+        fn named_values() {
+            let values_list = parse_values_list(r#"v => [ok => 1, 2, overflow => u32::MAX]"#);
+
+            assert_eq!(values_list.args(), to_args!(["1", "2", "u32::MAX"]));
+            assert_eq!(
+                vec!["ok", "2", "overflow"],
+                values_list
+                    .values
+                    .iter()
+                    .map(Value::description)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn values_attributes() {
+            let values_list = parse_values_list(
+                r#"v => [#[should_panic] 1, 2, #[ignore] #[other] big => 42, #[a] a]"#,
+            );
+
+            assert_eq!(values_list.args(), to_args!(["1", "2", "42", "a"]));
+            assert_eq!(
+                vec![
+                    attrs("#[should_panic]"),
+                    vec![],
+                    attrs("#[ignore] #[other]"),
+                    attrs("#[a]")
+                ],
+                values_list
+                    .values
+                    .into_iter()
+                    .map(|v| v.attrs)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[rstest]
+        #[case::comparison("a == b")]
+        #[case::greater_or_equal("a >= b")]
+        #[case::closure("|a| a + 1")]
+        #[case::match_expr("match a { _ => 1 }")]
+        fn not_confuse_expressions_with_names(#[case] code: &str) {
+            let value: Value = code.ast();
+
+            assert_eq!(None, value.description);
+            assert_eq!(expr(code), value.expr);
+        }
+
         #[test]
         #[should_panic(expected = r#"expected square brackets"#)]
         fn forget_brackets() {
@@ -0,0 +1,69 @@
+use syn::{Attribute, LitBool, LitStr, Meta};
+
+/// Arguments of the `#[xfail]` attribute: `#[xfail(reason = "...", strict = false)]`. Both are
+/// optional.
+#[derive(Debug, PartialEq, Default, Clone)]
+pub(crate) struct XFail {
+    pub(crate) reason: Option<LitStr>,
+    pub(crate) strict: Option<LitBool>,
+}
+
+impl XFail {
+    pub(crate) fn from_attr(attr: &Attribute) -> syn::Result<Self> {
+        let mut xfail = Self::default();
+        if let Meta::Path(_) = attr.meta {
+            return Ok(xfail);
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("reason") {
+                xfail.reason = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("strict") {
+                xfail.strict = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("Invalid xfail argument: use `reason` or `strict`"));
+            }
+            Ok(())
+        })?;
+        Ok(xfail)
+    }
+
+    pub(crate) fn reason(&self) -> String {
+        self.reason.as_ref().map(LitStr::value).unwrap_or_default()
+    }
+
+    pub(crate) fn is_strict(&self) -> bool {
+        self.strict.as_ref().map(|s| s.value).unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn xfail(code: &str) -> syn::Result<XFail> {
+        XFail::from_attr(&attrs(code)[0])
+    }
+
+    #[rstest]
+    #[case::no_args("#[xfail]", "", true)]
+    #[case::empty("#[xfail()]", "", true)]
+    #[case::reason(r#"#[xfail(reason = "known bug")]"#, "known bug", true)]
+    #[case::not_strict("#[xfail(strict = false)]", "", false)]
+    #[case::both(r#"#[xfail(strict = false, reason = "bug")]"#, "bug", false)]
+    fn parse(#[case] code: &str, #[case] reason: &str, #[case] strict: bool) {
+        let xfail = xfail(code).unwrap();
+
+        assert_eq!(reason, xfail.reason());
+        assert_eq!(strict, xfail.is_strict());
+    }
+
+    #[rstest]
+    #[case::unknown_arg(r#"#[xfail(because = "bug")]"#)]
+    #[case::reason_not_a_string("#[xfail(reason = 42)]")]
+    #[case::strict_not_a_bool(r#"#[xfail(strict = "no")]"#)]
+    #[case::name_value(r#"#[xfail = "bug"]"#)]
+    fn return_error(#[case] code: &str) {
+        assert!(xfail(code).is_err());
+    }
+}
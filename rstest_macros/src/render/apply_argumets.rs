@@ -69,10 +69,18 @@ impl ApplyArgumets for ItemFn {
                 let a = &p.ident;
                 quote::quote! { let #p = #a.await; }
             });
+        let destructured_args = self
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|a| a.maybe_ident())
+            .filter_map(|id| arguments.pattern(id).map(|p| (id, p)))
+            .map(|(id, p)| quote::quote! { let #p = #id; });
         let orig_block_impl = self.block.clone();
         self.block = parse_quote! {
             {
                 #(#rebound_awaited_args)*
+                #(#destructured_args)*
                 #orig_block_impl
             }
         };
@@ -117,7 +125,10 @@ fn update_type_with_lifetime(ty: &mut Type, ident: Ident) -> Option<Lifetime> {
 #[cfg(test)]
 mod should {
     use super::*;
-    use crate::test::{assert_eq, *};
+    use crate::{
+        parse::arguments::FutureArg,
+        test::{assert_eq, *},
+    };
     use syn::ItemFn;
 
     #[rstest]
@@ -218,8 +229,6 @@ mod should {
     mod await_future_args {
         use rstest_test::{assert_in, assert_not_in};
 
-        use crate::parse::arguments::FutureArg;
-
         use super::*;
 
         #[test]
@@ -267,4 +276,26 @@ mod should {
             assert_in!(code, mut_await_argument_code_string("a"));
         }
     }
+
+    #[test]
+    fn destructure_patterns_after_await() {
+        let mut item_fn: ItemFn = r#"fn test(arg_1: (u32, u32), b: u32) { body(); }"#.ast();
+        let mut arguments: ArgumentsInfo = Default::default();
+        arguments.set_future(ident("arg_1"), FutureArg::Await);
+        arguments.set_patterns(std::iter::once((
+            ident("arg_1"),
+            parse_quote! { (a, mut b) },
+        )));
+
+        item_fn.apply_argumets(&arguments);
+
+        let expected: syn::Block = parse_quote! {
+            {
+                let arg_1 = arg_1.await;
+                let (a, mut b) = arg_1;
+                { body(); }
+            }
+        };
+        assert_eq!(expected.display_code(), item_fn.block.display_code());
+    }
 }
@@ -1,12 +1,13 @@
 use proc_macro2::{Span, TokenStream};
-use syn::{parse_quote, Ident, ItemFn, ReturnType};
+use syn::{parse_quote, FnArg, Ident, ItemFn, ReturnType, Type};
 
 use quote::quote;
 
 use super::apply_argumets::ApplyArgumets;
-use super::{inject, render_exec_call};
+use super::{inject, render_exec_call, trace_value};
+use crate::refident::MaybeIdent;
 use crate::resolver::{self, Resolver};
-use crate::utils::{fn_args, fn_args_idents};
+use crate::utils::{fn_args, fn_args_idents, type_uses_generics};
 use crate::{parse::fixture::FixtureInfo, utils::generics_clean_up};
 
 fn wrap_return_type_as_static_ref(rt: ReturnType) -> ReturnType {
@@ -32,6 +33,56 @@ fn wrap_call_impl_with_call_once_impl(call_impl: TokenStream, rt: &ReturnType) -
     }
 }
 
+/// Fixtures are traced only when resolved by a test marked by `#[trace(fixtures)]`: the
+/// resolutions of the fixtures used to build this one are nested in this scope.
+fn render_fixture_scope(name: &Ident, method: &str) -> TokenStream {
+    quote! {
+        let _rstest_fixture_scope = rstest::trace::FixtureScope::enter(
+            concat!(module_path!(), "::", stringify!(#name)),
+            #method,
+        );
+    }
+}
+
+/// Trace the fixture arguments and the built value, if the fixtures are traced. Values of
+/// generic types are traced just by their type name: we cannot know which traits they
+/// implement.
+fn render_trace_built(
+    fixture: &ItemFn,
+    output: &ReturnType,
+    call_impl: TokenStream,
+) -> TokenStream {
+    let name = &fixture.sig.ident;
+    let generics = &fixture.sig.generics;
+    let trace = |arg: &Ident, t: &Type| {
+        if type_uses_generics(t, generics) {
+            quote! {
+                rstest::trace::ViaTypeName::trace_format(&rstest::trace::Printable(&#arg))
+            }
+        } else {
+            trace_value(arg)
+        }
+    };
+    let (args, values): (Vec<_>, Vec<_>) = fn_args(fixture)
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pt) => arg.maybe_ident().map(|id| (id, trace(id, &pt.ty))),
+            _ => None,
+        })
+        .unzip();
+    let value = match output {
+        ReturnType::Type(_, t) => trace(&parse_quote! { rstest_value }, t),
+        ReturnType::Default => trace_value(&parse_quote! { rstest_value }),
+    };
+    quote! {
+        let rstest_trace_arguments = rstest::trace::fixture_arguments(
+            || vec![#((stringify!(#args), #values)),*]
+        );
+        let rstest_value = { #call_impl };
+        rstest::trace::fixture_built(stringify!(#name), rstest_trace_arguments, || #value);
+        rstest_value
+    }
+}
+
 pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     fixture.apply_argumets(&info.arguments);
     let name = &fixture.sig.ident;
@@ -73,6 +124,8 @@ pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
         output = wrap_return_type_as_static_ref(output);
         default_output = wrap_return_type_as_static_ref(default_output);
     }
+    let call_impl = render_trace_built(&fixture, &output, call_impl);
+    let default_scope = render_fixture_scope(name, "default");
 
     quote! {
         #[allow(non_camel_case_types)]
@@ -86,6 +139,7 @@ pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
             }
 
             pub #asyncness fn default #default_generics () #default_output #default_where_clause {
+                #default_scope
                 #inject
                 #call_get
             }
@@ -128,6 +182,7 @@ fn render_partial_impl(
     let sign_args = fn_args(fixture).take(n);
     let fixture_args = fn_args_idents(fixture).cloned().collect::<Vec<_>>();
     let name = Ident::new(&format!("partial_{n}"), Span::call_site());
+    let scope = render_fixture_scope(&fixture.sig.ident, &name.to_string());
 
     let call_get = render_exec_call(
         parse_quote! { Self::get },
@@ -138,6 +193,7 @@ fn render_partial_impl(
     quote! {
         #[allow(unused_mut)]
         pub #asyncness fn #name #generics (#(#sign_args),*) #output #where_clause {
+            #scope
             #inject
             #call_get
         }
@@ -289,11 +345,7 @@ mod should {
         ));
 
         let body = select_method(out.core_impl, method).unwrap().block;
-        let last_statment = body.stmts.last().unwrap();
-        let is_await = match last_statment {
-            syn::Stmt::Expr(syn::Expr::Await(_), _) => true,
-            _ => false,
-        };
+        let is_await = body.display_code().contains(". await");
 
         assert_eq!(is_async, is_await);
     }
@@ -571,4 +623,58 @@ mod should {
         assert_in!(code, await_argument_code_string("b"));
         assert_not_in!(code, await_argument_code_string("c"));
     }
+
+    #[rstest]
+    #[case::default("default")]
+    #[case::partial("partial_1")]
+    fn open_a_fixture_trace_scope_in_resolution_methods(#[case] method: &str) {
+        let (_, out) = parse_fixture(r#"pub fn test(a: u32) -> u32 { a }"#);
+
+        let code = select_method(out.core_impl, method)
+            .unwrap()
+            .block
+            .display_code();
+
+        assert_in!(code, "rstest :: trace :: FixtureScope :: enter");
+        assert_in!(code, format!(r#"stringify ! (test)) , "{method}""#));
+    }
+
+    #[test]
+    fn trace_the_built_value_in_get_method() {
+        let (_, out) = parse_fixture(r#"pub fn test(a: u32, b: &str) -> u32 { a }"#);
+
+        let code = select_method(out.core_impl, "get")
+            .unwrap()
+            .block
+            .display_code();
+
+        assert_in!(code, "rstest :: trace :: fixture_arguments");
+        assert_in!(code, "(stringify ! (a) , ");
+        assert_in!(code, "(stringify ! (b) , ");
+        assert_in!(code, "rstest :: trace :: fixture_built (stringify ! (test)");
+    }
+
+    #[test]
+    fn trace_just_type_name_of_generic_values() {
+        let (_, out) = parse_fixture(r#"pub fn test<T: Tr>(a: u32, t: T, i: impl Tr) -> T { t }"#);
+
+        let code = select_method(out.core_impl, "get")
+            .unwrap()
+            .block
+            .display_code();
+
+        assert_in!(code, "Printable (& a)) . trace_format ()");
+        assert_in!(
+            code,
+            "ViaTypeName :: trace_format (& rstest :: trace :: Printable (& t))"
+        );
+        assert_in!(
+            code,
+            "ViaTypeName :: trace_format (& rstest :: trace :: Printable (& i))"
+        );
+        assert_in!(
+            code,
+            "ViaTypeName :: trace_format (& rstest :: trace :: Printable (& rstest_value))"
+        );
+    }
 }
@@ -2,23 +2,33 @@ pub(crate) mod fixture;
 mod test;
 mod wrapper;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use syn::token::Async;
 
 use proc_macro2::{Span, TokenStream};
-use syn::{parse_quote, Attribute, Expr, FnArg, Ident, ItemFn, Path, ReturnType, Stmt};
+use syn::{parse_quote, Attribute, Expr, FnArg, Ident, ItemFn, Path, ReturnType};
 
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 
-use crate::utils::{attr_ends_with, sanitize_ident};
+use crate::utils::{attr_ends_with, sanitize_ident, type_uses_generics};
 use crate::{
     parse::{
+        arguments::ArgumentsInfo,
+        repeat::Repeat,
+        retry::Retry,
         rstest::{RsTestAttributes, RsTestData, RsTestInfo},
+        runtimes::Runtimes,
+        serial::Keys,
+        skip_if::SkipIf,
+        tag::Tags,
         testcase::TestCase,
-        vlist::ValueList,
+        timeout::Timeout,
+        trace::Trace,
+        vlist::{Value, ValueList},
+        xfail::XFail,
     },
-    utils::attr_is,
+    utils::{attr_is, attr_is_rstest},
 };
 use crate::{
     refident::MaybeIdent,
@@ -28,12 +38,16 @@ use wrapper::WrapByModule;
 
 pub(crate) use fixture::render as fixture;
 
-use self::apply_argumets::ApplyArgumets;
+use self::{apply_argumets::ApplyArgumets, runtime::Runtime};
 pub(crate) mod apply_argumets;
 pub(crate) mod inject;
+pub(crate) mod runtime;
 
 pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
     test.apply_argumets(&info.arguments);
+    if has_timeout(&test, &info.data) {
+        track_await_phase(&mut test, &info.arguments);
+    }
     let resolver = resolver::fixtures::get(info.data.fixtures());
     let args = test.sig.inputs.iter().cloned().collect::<Vec<_>>();
     let attrs = std::mem::take(&mut test.attrs);
@@ -49,7 +63,9 @@ pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         Some(&test),
         resolver,
         &info.attributes,
+        &info.arguments,
         &test.sig.generics,
+        &Default::default(),
     )
 }
 
@@ -60,13 +76,16 @@ pub(crate) fn parametrize(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         arguments,
     } = info;
     test.apply_argumets(&arguments);
+    if has_timeout(&test, &data) {
+        track_await_phase(&mut test, &arguments);
+    }
     let resolver_fixtures = resolver::fixtures::get(data.fixtures());
 
-    let rendered_cases = cases_data(&data, test.sig.ident.span())
-        .map(|(name, attrs, resolver)| {
-            TestCaseRender::new(name, attrs, (resolver, &resolver_fixtures))
+    let rendered_cases = cases_data(&data, test.sig.ident.span(), attributes.stable_names())
+        .map(|(name, attrs, resolver, report)| {
+            TestCaseRender::new(name, attrs, (resolver, &resolver_fixtures), report)
         })
-        .map(|case| case.render(&test, &attributes))
+        .map(|case| case.render(&test, &attributes, &arguments))
         .collect();
 
     test_group(test, rendered_cases)
@@ -78,32 +97,53 @@ impl ValueList {
         test: &ItemFn,
         resolver: &dyn Resolver,
         attrs: &[syn::Attribute],
+        report: &ReportInfo,
         attributes: &RsTestAttributes,
+        arguments: &ArgumentsInfo,
     ) -> TokenStream {
         let span = test.sig.ident.span();
         let test_cases = self
             .argument_data(resolver)
-            .map(|(name, r)| TestCaseRender::new(Ident::new(&name, span), attrs, r))
-            .map(|test_case| test_case.render(test, attributes));
+            .map(|(name, value_attrs, r, value)| {
+                let attrs = [attrs, value_attrs].concat();
+                let report = report.with_parameter(&self.arg, value.description());
+                TestCaseRender::new(Ident::new(&name, span), &attrs, r, report)
+                    .render(test, attributes, arguments)
+            });
 
         quote! { #(#test_cases)* }
     }
 
+    fn value_name(&self, index: usize, value: &Value) -> String {
+        let description = sanitize_ident(&value.description());
+        format!(
+            "{}_{:0len$}_{description:.64}",
+            self.arg,
+            index + 1,
+            len = self.values.len().display_len()
+        )
+    }
+
     fn argument_data<'a>(
         &'a self,
         resolver: &'a dyn Resolver,
-    ) -> impl Iterator<Item = (String, Box<(&'a dyn Resolver, (String, Expr))>)> + 'a {
-        let max_len = self.values.len();
+    ) -> impl Iterator<
+        Item = (
+            String,
+            &'a [syn::Attribute],
+            Box<(&'a dyn Resolver, (String, Expr))>,
+            &'a Value,
+        ),
+    > + 'a {
         self.values.iter().enumerate().map(move |(index, value)| {
-            let description = sanitize_ident(&value.description());
-            let name = format!(
-                "{}_{:0len$}_{description:.64}",
-                self.arg,
-                index + 1,
-                len = max_len.display_len()
-            );
+            let name = self.value_name(index, value);
             let resolver_this = (self.arg.to_string(), value.expr.clone());
-            (name, Box::new((resolver, resolver_this)))
+            (
+                name,
+                value.attrs.as_slice(),
+                Box::new((resolver, resolver_this)),
+                value,
+            )
         })
     }
 }
@@ -113,7 +153,9 @@ fn _matrix_recursive<'a>(
     list_values: &'a [&'a ValueList],
     resolver: &dyn Resolver,
     attrs: &'a [syn::Attribute],
+    report: &ReportInfo,
     attributes: &RsTestAttributes,
+    arguments: &ArgumentsInfo,
 ) -> TokenStream {
     if list_values.is_empty() {
         return Default::default();
@@ -126,13 +168,26 @@ fn _matrix_recursive<'a>(
         attrs.push(parse_quote!(
             #[allow(non_snake_case)]
         ));
-        vlist.render(test, resolver, &attrs, attributes)
+        vlist.render(test, resolver, &attrs, report, attributes, arguments)
     } else {
         let span = test.sig.ident.span();
-        let modules = vlist.argument_data(resolver).map(move |(name, resolver)| {
-            _matrix_recursive(test, list_values, &resolver, attrs, attributes)
-                .wrap_by_mod(&Ident::new(&name, span))
-        });
+        let modules =
+            vlist
+                .argument_data(resolver)
+                .map(move |(name, value_attrs, resolver, value)| {
+                    let attrs = [attrs, value_attrs].concat();
+                    let report = report.with_parameter(&vlist.arg, value.description());
+                    _matrix_recursive(
+                        test,
+                        list_values,
+                        &resolver,
+                        &attrs,
+                        &report,
+                        attributes,
+                        arguments,
+                    )
+                    .wrap_by_mod(&Ident::new(&name, span))
+                });
 
         quote! { #(
             #[allow(non_snake_case)]
@@ -148,25 +203,42 @@ pub(crate) fn matrix(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         arguments,
     } = info;
     test.apply_argumets(&arguments);
+    if has_timeout(&test, &data) {
+        track_await_phase(&mut test, &arguments);
+    }
     let span = test.sig.ident.span();
 
-    let cases = cases_data(&data, span).collect::<Vec<_>>();
+    let cases = cases_data(&data, span, attributes.stable_names()).collect::<Vec<_>>();
 
     let resolver = resolver::fixtures::get(data.fixtures());
+    if attributes.flat() {
+        let rendered_cases = flat_matrix(&test, &data, cases, &resolver, &attributes, &arguments);
+        return test_group(test, rendered_cases);
+    }
     let rendered_cases = if cases.is_empty() {
         let list_values = data.list_values().collect::<Vec<_>>();
-        _matrix_recursive(&test, &list_values, &resolver, &[], &attributes)
+        _matrix_recursive(
+            &test,
+            &list_values,
+            &resolver,
+            &[],
+            &Default::default(),
+            &attributes,
+            &arguments,
+        )
     } else {
         cases
             .into_iter()
-            .map(|(case_name, attrs, case_resolver)| {
+            .map(|(case_name, attrs, case_resolver, report)| {
                 let list_values = data.list_values().collect::<Vec<_>>();
                 _matrix_recursive(
                     &test,
                     &list_values,
                     &(case_resolver, &resolver),
                     attrs,
+                    &report,
                     &attributes,
+                    &arguments,
                 )
                 .wrap_by_mod(&case_name)
             })
@@ -176,12 +248,106 @@ pub(crate) fn matrix(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
     test_group(test, rendered_cases)
 }
 
-fn resolve_default_test_attr(is_async: bool) -> TokenStream {
-    if is_async {
-        quote! { #[async_std::test] }
+/// All the combinations of the values lists as test name, values attributes, resolver and
+/// chosen values.
+#[allow(clippy::type_complexity)]
+fn flat_matrix_data<'a>(
+    list_values: &[&'a ValueList],
+) -> Vec<(
+    String,
+    Vec<syn::Attribute>,
+    HashMap<String, &'a Expr>,
+    ReportInfo,
+)> {
+    list_values.iter().fold(
+        vec![(String::new(), vec![], HashMap::new(), ReportInfo::default())],
+        |combinations, vlist| {
+            combinations
+                .into_iter()
+                .flat_map(|(name, attrs, resolver, report)| {
+                    vlist.values.iter().enumerate().map(move |(index, value)| {
+                        let mut resolver = resolver.clone();
+                        resolver.insert(vlist.arg.to_string(), &value.expr);
+                        (
+                            join_test_names(&name, &vlist.value_name(index, value)),
+                            [attrs.as_slice(), &value.attrs].concat(),
+                            resolver,
+                            report.with_parameter(&vlist.arg, value.description()),
+                        )
+                    })
+                })
+                .collect()
+        },
+    )
+}
+
+fn join_test_names(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_owned()
     } else {
-        quote! { #[test] }
+        format!("{prefix}__{name}")
+    }
+}
+
+/// Render all the matrix combinations (eventually for each case) as sibling test functions
+/// instead of nested modules.
+fn flat_matrix<'a>(
+    test: &ItemFn,
+    data: &'a RsTestData,
+    cases: Vec<CaseData<'a>>,
+    resolver: &dyn Resolver,
+    attributes: &RsTestAttributes,
+    arguments: &ArgumentsInfo,
+) -> TokenStream {
+    let list_values = data.list_values().collect::<Vec<_>>();
+    let combinations = flat_matrix_data(&list_values);
+    let tests = if cases.is_empty() {
+        combinations
+    } else {
+        cases
+            .into_iter()
+            .flat_map(|(case_name, case_attrs, case_resolver, case_report)| {
+                combinations
+                    .iter()
+                    .map(move |(name, attrs, values_resolver, values_report)| {
+                        let mut resolver = case_resolver.clone();
+                        resolver.extend(values_resolver.iter().map(|(k, &v)| (k.clone(), v)));
+                        (
+                            join_test_names(&case_name.to_string(), name),
+                            [case_attrs, attrs].concat(),
+                            resolver,
+                            case_report.extend(values_report),
+                        )
+                    })
+            })
+            .collect()
+    };
+
+    let mut names = HashSet::new();
+    if let Some((name, _, _, _)) = tests.iter().find(|(name, _, _, _)| !names.insert(name)) {
+        return syn::Error::new(
+            test.sig.ident.span(),
+            format!("Duplicate test name '{name}' in flat matrix: rename some cases or values."),
+        )
+        .to_compile_error();
     }
+
+    let span = test.sig.ident.span();
+    tests
+        .into_iter()
+        .map(|(name, mut attrs, values_resolver, report)| {
+            attrs.push(parse_quote!(
+                #[allow(non_snake_case)]
+            ));
+            TestCaseRender::new(
+                Ident::new(&name, span),
+                &attrs,
+                (values_resolver, resolver),
+                report,
+            )
+            .render(test, attributes, arguments)
+        })
+        .collect()
 }
 
 fn render_exec_call(fn_path: Path, args: &[Ident], is_async: bool) -> TokenStream {
@@ -192,27 +358,195 @@ fn render_exec_call(fn_path: Path, args: &[Ident], is_async: bool) -> TokenStrea
     }
 }
 
+/// Run the test in a child process: `by_env` tests are isolated only if the `RSTEST_ISOLATE`
+/// environment variable is set when the test runs. The parent process takes the `serial`
+/// locks, handles the timeout and reports the outcome described by `report`, if any.
+fn render_isolate(
+    name: &Ident,
+    timeout: Option<&Timeout>,
+    should_panic: bool,
+    by_env: bool,
+    serial: Option<TokenStream>,
+    passed: &TokenStream,
+    report: Option<TokenStream>,
+) -> TokenStream {
+    let timeout = match timeout {
+        Some(Timeout { duration, .. }) => quote! { Some(#duration) },
+        None => quote! { rstest::timeout::default_timeout() },
+    };
+    let by_env = by_env.then(|| quote! { rstest::isolate::by_env() && });
+    let run = quote! {
+        #serial
+        rstest::isolate::run(&test_name, #timeout, #should_panic);
+        #passed
+    };
+    let run = match report {
+        Some(test) => quote! { rstest::report::run(#test, move || { #run }, |_| None) },
+        None => quote! { { #run } },
+    };
+    quote! {
+        let test_name = rstest::isolate::test_name(module_path!(), stringify!(#name));
+        if #by_env !rstest::isolate::is_isolated(&test_name) {
+            return #run;
+        }
+    }
+}
+
+/// The stopwatch warns if the passed test takes more than `#[warn_slow]` threshold or the
+/// `RSTEST_WARN_SLOW` default one.
+fn render_stopwatch(name: &Ident, threshold: Option<&Expr>, body: TokenStream) -> TokenStream {
+    let threshold = match threshold {
+        Some(threshold) => quote! { Some(#threshold) },
+        None => quote! { rstest::slow::default_threshold() },
+    };
+    quote! {
+        let _rstest_stopwatch = rstest::slow::Stopwatch::start(
+            rstest::isolate::test_name(module_path!(), stringify!(#name)),
+            #threshold,
+        );
+        let rstest_output = { #body };
+        if { use rstest::outcome::*; (&&&Failure(&rstest_output)).failure().is_some() } {
+            _rstest_stopwatch.failed();
+        }
+        rstest_output
+    }
+}
+
+fn render_xfail(execute: TokenStream, xfail: &XFail, is_async: bool) -> TokenStream {
+    let reason = xfail.reason();
+    let strict = xfail.is_strict();
+    let outcome = if is_async {
+        quote! { rstest::outcome::catch_async(async move { #execute }).await }
+    } else {
+        quote! { rstest::outcome::catch_sync(move || { #execute }) }
+    };
+    quote! {
+        rstest::xfail::check(#outcome, #reason, #strict)
+    }
+}
+
+/// Benchmarks measure just the test function call: the `RSTEST_BENCH` samples are declared
+/// in the rendered test.
+fn render_measure(exec_call: TokenStream, is_async: bool) -> TokenStream {
+    if is_async {
+        quote! { RSTEST_BENCH.measure_async(async move { #exec_call }).await }
+    } else {
+        quote! { RSTEST_BENCH.measure(move || #exec_call) }
+    }
+}
+
+/// Benchmarks run the whole test body again and again, when enabled by `RSTEST_BENCH`
+/// environment variable: the fixtures are resolved for every call.
+fn render_bench(name: &Ident, body: &TokenStream, is_async: bool) -> TokenStream {
+    let test = quote! { &rstest::isolate::test_name(module_path!(), stringify!(#name)) };
+    if is_async {
+        quote! { rstest::bench::run_async(&RSTEST_BENCH, #test, || async move { #body }).await }
+    } else {
+        quote! { rstest::bench::run(&RSTEST_BENCH, #test, || { #body }) }
+    }
+}
+
+/// Every iteration runs the whole test body: the fixtures are resolved again and the timeout
+/// applies to each iteration.
+fn render_repeat(name: &Ident, repeat: &Repeat, body: &TokenStream, is_async: bool) -> TokenStream {
+    let iterations = repeat.iterations;
+    let test = quote! { &rstest::isolate::test_name(module_path!(), stringify!(#name)) };
+    match repeat.threads {
+        _ if is_async => quote! {
+            rstest::repeat::run_async(#test, #iterations, || async move { #body }).await
+        },
+        Some(threads) => quote! {
+            rstest::repeat::run_concurrent(#test, #iterations, #threads, || { #body })
+        },
+        None => quote! { rstest::repeat::run(#test, #iterations, || { #body }) },
+    }
+}
+
+/// Every attempt runs the whole test body: the fixtures are resolved again and the timeout
+/// applies to each attempt.
+fn render_retry(name: &Ident, retry: &Retry, body: &TokenStream, is_async: bool) -> TokenStream {
+    let retries = retry.retries;
+    let test = quote! { &rstest::isolate::test_name(module_path!(), stringify!(#name)) };
+    if is_async {
+        quote! { rstest::retry::run_async(#test, #retries, || async move { #body }).await }
+    } else {
+        quote! { rstest::retry::run(#test, #retries, || { #body }) }
+    }
+}
+
+/// Render the test function call: `prelude` resolves the arguments and is executed under the
+/// test timeout. If the test doesn't have an explicit timeout and `default_timeout` is `true`
+/// the `RSTEST_TIMEOUT` default is resolved when the test runs: sync tests that aren't `Send`
+/// and `'static` run without it.
 fn render_test_call(
-    fn_path: Path,
-    args: &[Ident],
-    timeout: Option<Expr>,
+    exec_call: TokenStream,
+    prelude: TokenStream,
+    timeout: Option<Timeout>,
+    default_timeout: bool,
     is_async: bool,
 ) -> TokenStream {
-    let timeout = timeout.map(|x| quote! {#x}).or_else(|| {
-        std::env::var("RSTEST_TIMEOUT")
-            .ok()
-            .map(|to| quote! { std::time::Duration::from_secs( (#to).parse().unwrap()) })
-    });
     match (timeout, is_async) {
-        (Some(to_expr), true) => quote! {
+        (Some(Timeout { duration, .. }), true) => quote! {
+            use rstest::timeout::*;
+            execute_with_timeout_async(move || async move { #prelude #exec_call }, #duration).await
+        },
+        (
+            Some(Timeout {
+                duration,
+                watchdog: true,
+            }),
+            false,
+        ) => quote! {
             use rstest::timeout::*;
-            execute_with_timeout_async(move || #fn_path(#(#args),*), #to_expr).await
+            execute_with_watchdog_sync(move || { #prelude #exec_call }, #duration)
         },
-        (Some(to_expr), false) => quote! {
+        (Some(Timeout { duration, .. }), false) => quote! {
             use rstest::timeout::*;
-            execute_with_timeout_sync(move || #fn_path(#(#args),*), #to_expr)
+            execute_with_timeout_sync(move || { #prelude #exec_call }, #duration)
         },
-        _ => render_exec_call(fn_path, args, is_async),
+        (None, true) if default_timeout && cfg!(feature = "async-timeout") => quote! {
+            use rstest::timeout::*;
+            execute_with_default_timeout_async(move || async move { #prelude #exec_call }).await
+        },
+        (None, false) if default_timeout => quote! {
+            use rstest::timeout::*;
+            (&&&DefaultTimeout::new(move || { #prelude #exec_call })).execute_with_default_timeout()
+        },
+        _ => quote! {
+            #prelude
+            #exec_call
+        },
+    }
+}
+
+/// The phases are tracked just to report where a timeout expired: `true` if the function or
+/// any case or value has a `#[timeout]` attribute.
+fn has_timeout(test: &ItemFn, data: &RsTestData) -> bool {
+    test.attrs
+        .iter()
+        .chain(data.tests_attrs())
+        .any(|a| attr_is(a, "timeout"))
+}
+
+/// Mark the phase of awaiting `#[future(awt)]` arguments: they are awaited by the first
+/// statements of the test function.
+fn track_await_phase(test: &mut ItemFn, arguments: &ArgumentsInfo) {
+    let awaited = test
+        .sig
+        .inputs
+        .iter()
+        .filter_map(MaybeIdent::maybe_ident)
+        .filter(|id| arguments.is_future_await(id))
+        .count();
+    if awaited > 0 {
+        test.block.stmts.insert(
+            awaited,
+            parse_quote! { rstest::timeout::enter_phase(rstest::timeout::Phase::Body); },
+        );
+        test.block.stmts.insert(
+            0,
+            parse_quote! { rstest::timeout::enter_phase(rstest::timeout::Phase::Await); },
+        );
     }
 }
 
@@ -232,7 +566,7 @@ fn generics_types_ident<'a>(generics: &'a syn::Generics) -> impl Iterator<Item =
 /// * `resolver` - The resolver used to resolve injected values
 /// * `attributes` - Test attributes to select test behaviour
 /// * `generic_types` - The genrics type used in signature
-///
+/// * `report` - The case description and parameters to report
 // Ok I need some refactoring here but now that not a real issue
 #[allow(clippy::too_many_arguments)]
 fn single_test_case(
@@ -245,7 +579,9 @@ fn single_test_case(
     test_impl: Option<&ItemFn>,
     resolver: impl Resolver,
     attributes: &RsTestAttributes,
+    arguments: &ArgumentsInfo,
     generics: &syn::Generics,
+    report: &ReportInfo,
 ) -> TokenStream {
     let (attrs, trace_me): (Vec<_>, Vec<_>) =
         attrs.iter().cloned().partition(|a| !attr_is(a, "trace"));
@@ -253,14 +589,60 @@ fn single_test_case(
     if !trace_me.is_empty() {
         attributes.add_trace(format_ident!("trace"));
     }
+    // Already checked in error module
+    let trace = trace_me
+        .last()
+        .map(|attribute| Trace::from_attr(attribute).unwrap())
+        .unwrap_or_default();
     let generics_types = generics_types_ident(generics).cloned().collect::<Vec<_>>();
-    let inject = inject::resolve_aruments(args.iter(), &resolver, &generics_types);
+    // The phases are tracked just to report where the timeout expired
+    let track_phases = attrs.iter().any(|a| attr_is(a, "timeout"));
+    let (attrs, skip_ifs): (Vec<_>, Vec<_>) =
+        attrs.iter().cloned().partition(|a| !attr_is(a, "skip_if"));
+    // Already checked in error module
+    let skip_ifs = skip_ifs
+        .iter()
+        .map(|attribute| attribute.parse_args::<SkipIf>().unwrap())
+        .collect::<Vec<_>>();
+    // Arguments used by some skip conditions should be resolved before the others
+    let (skip_args, args_to_inject): (Vec<_>, Vec<_>) = args.iter().partition(|arg| {
+        arg.maybe_ident()
+            .map(|id| !arguments.skip_if(id).is_empty())
+            .unwrap_or_default()
+    });
+    let inject_skip_args =
+        inject::resolve_aruments(skip_args.iter().copied(), &resolver, &generics_types);
+    let args_skip_ifs = skip_args
+        .iter()
+        .filter_map(|arg| arg.maybe_ident())
+        .flat_map(|id| arguments.skip_if(id));
+    let inject = args_to_inject.into_iter().map(|arg| {
+        // Only the fixtures: the case and values arguments are just expressions
+        let phase = arg
+            .maybe_ident()
+            .filter(|_| track_phases)
+            .filter(|id| !report.parameters.iter().any(|(name, _)| id == &name))
+            .map(|id| {
+                quote! { rstest::timeout::enter_phase(rstest::timeout::Phase::Fixture(stringify!(#id))); }
+            });
+        let resolve = inject::resolve_aruments(std::iter::once(arg), &resolver, &generics_types);
+        quote! {
+            #phase
+            #resolve
+        }
+    });
+    let inject = quote! { #(#inject)* };
     let args = args
         .iter()
         .filter_map(MaybeIdent::maybe_ident)
         .cloned()
         .collect::<Vec<_>>();
-    let trace_args = trace_arguments(args.iter(), &attributes);
+    let traced = args
+        .iter()
+        .filter(|&arg| attributes.trace_me(arg))
+        .collect::<Vec<_>>();
+    let on_failure = trace.on_failure && !traced.is_empty();
+    let trace_args = trace_arguments(testfn_name, &traced, on_failure);
 
     let is_async = asyncness.is_some();
     let (attrs, timeouts): (Vec<_>, Vec<_>) =
@@ -268,73 +650,456 @@ fn single_test_case(
 
     let timeout = timeouts
         .into_iter()
+        .last()
+        .map(|attribute| attribute.parse_args::<Timeout>().unwrap());
+    let registry_timeout = timeout.as_ref().map(|timeout| timeout.duration.clone());
+    let (attrs, warn_slows): (Vec<_>, Vec<_>) = attrs
+        .iter()
+        .cloned()
+        .partition(|a| !attr_is(a, "warn_slow"));
+    // Already checked in error module
+    let warn_slow = warn_slows
         .last()
         .map(|attribute| attribute.parse_args::<Expr>().unwrap());
+    let (attrs, isolates): (Vec<_>, Vec<_>) =
+        attrs.iter().cloned().partition(|a| !attr_is(a, "isolate"));
+    let isolated = !isolates.is_empty();
+    // The timeout of isolated tests is handled by the parent process: the tests isolated by
+    // `RSTEST_ISOLATE` keep their own timeout to use it if they're not isolated
+    let (timeout, isolate_timeout) = if isolated {
+        (None, Some(timeout))
+    } else {
+        let isolate_timeout = cfg!(feature = "isolate").then(|| timeout.clone());
+        (timeout, isolate_timeout)
+    };
+    let default_timeout = !isolated;
+    let (attrs, xfails): (Vec<_>, Vec<_>) =
+        attrs.iter().cloned().partition(|a| !attr_is(a, "xfail"));
+    // Already checked in error module
+    let xfail = xfails
+        .last()
+        .map(|attribute| XFail::from_attr(attribute).unwrap());
 
-    // If no injected attribut provided use the default one
-    let test_attr = if attrs
+    let (attrs, tags): (Vec<_>, Vec<_>) = attrs.iter().cloned().partition(|a| !attr_is(a, "tag"));
+    // Already checked in error module: the case and values tags combine with the function ones
+    let mut report = report.clone();
+    for tag in tags
         .iter()
-        .any(|a| attr_ends_with(a, &parse_quote! {test}))
+        .flat_map(|attribute| attribute.parse_args::<Tags>().unwrap().0)
     {
-        None
+        let tag = tag.to_string();
+        if !report.tags.contains(&tag) {
+            report.tags.push(tag);
+        }
+    }
+    let report = &report;
+
+    let (attrs, serials): (Vec<_>, Vec<_>) = attrs
+        .iter()
+        .cloned()
+        .partition(|a| !attr_is_rstest(a, "serial") && !attr_is_rstest(a, "parallel"));
+    // Already checked in error module: a key both serial and parallel is taken exclusively
+    let mut serial_keys: Vec<(String, bool)> = Vec::new();
+    for attribute in &serials {
+        let exclusive = attr_is_rstest(attribute, "serial");
+        for key in Keys::from_attr(attribute).unwrap().names() {
+            match serial_keys.iter_mut().find(|(k, _)| k == &key) {
+                Some((_, e)) => *e |= exclusive,
+                None => serial_keys.push((key, exclusive)),
+            }
+        }
+    }
+
+    let (attrs, repeats): (Vec<_>, Vec<_>) =
+        attrs.iter().cloned().partition(|a| !attr_is(a, "repeat"));
+    // Already checked in error module
+    let repeat = repeats
+        .last()
+        .map(|attribute| attribute.parse_args::<Repeat>().unwrap());
+    let (attrs, retries): (Vec<_>, Vec<_>) =
+        attrs.iter().cloned().partition(|a| !attr_is(a, "retry"));
+    // Already checked in error module
+    let retry = retries
+        .last()
+        .map(|attribute| attribute.parse_args::<Retry>().unwrap());
+
+    let (attrs, runtimes): (Vec<_>, Vec<_>) =
+        attrs.iter().cloned().partition(|a| !attr_is(a, "runtimes"));
+    // Already checked in error module
+    let runtimes = runtimes
+        .last()
+        .map(|attribute| attribute.parse_args::<Runtimes>().unwrap().take());
+
+    // If no injected attribut provided use the default one
+    let injected = attrs
+        .iter()
+        .any(|a| attr_ends_with(a, &parse_quote! {test}));
+    let should_panic = attrs.iter().any(|a| attr_is(a, "should_panic"));
+    // Fixtures are resolved in the test call to apply the timeout also to them
+    let trace_fixtures = trace
+        .fixtures
+        .then(|| quote! { let _rstest_trace_fixtures = rstest::trace::TraceFixtures::start(); });
+    let body_phase = track_phases
+        .then(|| quote! { rstest::timeout::enter_phase(rstest::timeout::Phase::Body); });
+    let prelude = quote! {
+        #trace_fixtures
+        #inject
+        #trace_args
+        #body_phase
+    };
+    let mut exec_call = render_exec_call(testfn_name.clone().into(), &args, is_async);
+    let bench = attributes.bench();
+    if bench {
+        exec_call = render_measure(exec_call, is_async);
+    }
+    if on_failure {
+        exec_call = quote! { _rstest_trace.check(#exec_call) };
+    }
+    let mut execute = render_test_call(exec_call, prelude, timeout, default_timeout, is_async);
+    let mut output = output.clone();
+    if let Some(xfail) = xfail {
+        execute = render_xfail(execute, &xfail, is_async);
+        // The outcome is checked by xfail: the test should not return the test result
+        output = ReturnType::Default;
+    }
+    let passed = render_passed(&output, generics);
+    let skip_ifs = skip_ifs.iter().map(|skip_if| skip_if.render(&passed));
+    let args_skip_ifs = args_skip_ifs.map(|skip_if| skip_if.render(&passed));
+    let body = quote! {
+        #(#skip_ifs)*
+        #inject_skip_args
+        #(#args_skip_ifs)*
+        #execute
+    };
+    let render_test_fn = |name: &Ident, runtime: Option<Runtime>| {
+        let test_attr = match runtime {
+            _ if injected => None,
+            Some(runtime) => Some(runtime.test_attr()),
+            None => Some(quote! { #[test] }),
+        };
+        let test_name = trace_args.is_some().then(|| {
+            quote! {
+                let rstest_test_name = rstest::isolate::test_name(module_path!(), stringify!(#name));
+            }
+        });
+        let body = quote! {
+            #test_name
+            #body
+        };
+        let body = if bench {
+            render_bench(name, &body, is_async)
+        } else {
+            body
+        };
+        let body = match &repeat {
+            Some(repeat) => render_repeat(name, repeat, &body, is_async),
+            None => body,
+        };
+        let body = match &retry {
+            Some(retry) => render_retry(name, retry, &body, is_async),
+            None => body,
+        };
+        // The `tags` feature skips also the untagged tests when `RSTEST_TAGS` requires some tags
+        let tags_check = (!report.tags.is_empty() || cfg!(feature = "tags"))
+            .then(|| render_tags_check(&report.tags, &passed));
+        // The isolated tests take the locks in the parent process
+        let serial = (!isolated)
+            .then(|| render_serial(&serial_keys, is_async))
+            .flatten();
+        let body = quote! {
+            #tags_check
+            #serial
+            #body
+        };
+        let body = if cfg!(feature = "report") {
+            render_report(name, testfn_name, report, should_panic, &body, is_async)
+        } else {
+            body
+        };
+        let (asyncness, body) = match runtime {
+            Some(runtime) if !runtime.is_async_fn() => (None, runtime.wrap_body(body)),
+            Some(runtime) => (asyncness, runtime.wrap_body(body)),
+            None => (asyncness, body),
+        };
+        let isolate = isolate_timeout.as_ref().map(|timeout| {
+            let serial = render_serial(&serial_keys, false);
+            let report = cfg!(feature = "report")
+                .then(|| render_report_test(name, testfn_name, report, should_panic));
+            render_isolate(
+                name,
+                timeout.as_ref(),
+                should_panic,
+                !isolated,
+                serial,
+                &passed,
+                report,
+            )
+        });
+        // The `warn-slow` feature enables the `RSTEST_WARN_SLOW` default threshold
+        let body = if warn_slow.is_some() || cfg!(feature = "warn-slow") {
+            render_stopwatch(name, warn_slow.as_ref(), body)
+        } else {
+            body
+        };
+        let samples = bench.then(|| {
+            quote! {
+                static RSTEST_BENCH: rstest::bench::Samples = rstest::bench::Samples::new();
+            }
+        });
+        let lifetimes = generics.lifetimes();
+        let register = cfg!(feature = "registry")
+            .then(|| render_register(name, testfn_name, report, registry_timeout.as_ref(), &attrs));
+        quote! {
+            #test_attr
+            #(#attrs)*
+            #asyncness fn #name<#(#lifetimes,)*>() #output {
+                #test_impl
+                #isolate
+                #samples
+                #body
+            }
+
+            #register
+        }
+    };
+
+    match runtimes {
+        // Render a test for each runtime
+        Some(runtimes) if is_async && !injected => {
+            let tests = runtimes
+                .into_iter()
+                .map(|runtime| render_test_fn(&runtime.ident(name.span()), Some(runtime)));
+            quote! { #(#tests)* }.wrap_by_mod(name)
+        }
+        _ => render_test_fn(name, (is_async && !injected).then(Runtime::default)),
+    }
+}
+
+/// The case description and the values of the parametrized arguments of a rendered test, as
+/// written in the source code, and its tags.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct ReportInfo {
+    case: Option<String>,
+    parameters: Vec<(String, String)>,
+    tags: Vec<String>,
+}
+
+impl ReportInfo {
+    fn with_parameter(&self, arg: &Ident, value: String) -> Self {
+        let mut report = self.clone();
+        report.parameters.push((arg.to_string(), value));
+        report
+    }
+
+    fn extend(&self, other: &Self) -> Self {
+        let mut report = self.clone();
+        report.parameters.extend(other.parameters.iter().cloned());
+        report
+    }
+}
+
+/// The test body reports its outcome, with the test parameters, when `RSTEST_REPORT_JSON` or
+/// `RSTEST_REPORT_JUNIT` environment variables are set: the returned values that aren't a
+/// `TestOutcome` are reported as passed.
+fn render_report(
+    name: &Ident,
+    testfn_name: &Ident,
+    report: &ReportInfo,
+    should_panic: bool,
+    body: &TokenStream,
+    is_async: bool,
+) -> TokenStream {
+    let test = render_report_test(name, testfn_name, report, should_panic);
+    let failure = quote! {
+        |output| { use rstest::outcome::*; (&&&Failure(output)).failure() }
+    };
+    if is_async {
+        quote! { rstest::report::run_async(#test, async move { #body }, #failure).await }
     } else {
-        Some(resolve_default_test_attr(is_async))
+        quote! { rstest::report::run(#test, move || { #body }, #failure) }
+    }
+}
+
+/// The `rstest::report::Test` that describes the rendered test in the reports.
+fn render_report_test(
+    name: &Ident,
+    testfn_name: &Ident,
+    report: &ReportInfo,
+    should_panic: bool,
+) -> TokenStream {
+    let case = match &report.case {
+        Some(case) => quote! { Some(#case) },
+        None => quote! { None },
     };
-    let execute = render_test_call(testfn_name.clone().into(), &args, timeout, is_async);
-    let lifetimes = generics.lifetimes();
+    let (args, values): (Vec<_>, Vec<_>) = report.parameters.iter().cloned().unzip();
+    let tags = &report.tags;
+    quote! {
+        rstest::report::Test::new(
+            module_path!(),
+            stringify!(#name),
+            stringify!(#testfn_name),
+            #case,
+            &[#((#args, #values)),*],
+            &[#(#tags),*],
+            #should_panic
+        )
+    }
+}
 
+/// Skip the test, returning `passed`, if its tags don't match the `RSTEST_TAGS` expression.
+fn render_tags_check(tags: &[String], passed: &TokenStream) -> TokenStream {
     quote! {
-        #test_attr
-        #(#attrs)*
-        #asyncness fn #name<#(#lifetimes,)*>() #output {
-            #test_impl
-            #inject
-            #trace_args
-            #execute
+        if let Some(reason) = rstest::tags::check(&[#(#tags),*]) {
+            rstest::skip::report(&reason);
+            return #passed;
         }
     }
 }
 
-fn trace_arguments<'a>(
-    args: impl Iterator<Item = &'a Ident>,
-    attributes: &RsTestAttributes,
-) -> Option<TokenStream> {
-    let mut statements = args
-        .filter(|&arg| attributes.trace_me(arg))
-        .map(|arg| {
-            let s: Stmt = parse_quote! {
-                println!("{} = {:?}", stringify!(#arg), #arg);
-            };
-            s
-        })
-        .peekable();
-    if statements.peek().is_some() {
-        Some(quote! {
-            println!("{:-^40}", " TEST ARGUMENTS ");
-            #(#statements)*
-            println!("{:-^40}", " TEST START ");
-        })
+/// The value returned by a test that passed without running its code: the output types
+/// that depend on generics should implement `Passed`, the other ones panic if they don't.
+fn render_passed(output: &ReturnType, generics: &syn::Generics) -> TokenStream {
+    match output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) if type_uses_generics(ty, generics) => {
+            quote! { rstest::outcome::Passed::passed() }
+        }
+        ReturnType::Type(_, ty) => quote! {
+            { use rstest::outcome::*; (&&&PassedValue::<#ty>(std::marker::PhantomData)).passed() }
+        },
+    }
+}
+
+/// Take the `#[rstest::serial]` and `#[rstest::parallel]` keys before resolving the fixtures: the guard is
+/// dropped at the end of the test, after the fixtures teardown. Async tests wait without
+/// blocking the executor.
+fn render_serial(keys: &[(String, bool)], is_async: bool) -> Option<TokenStream> {
+    if keys.is_empty() {
+        return None;
+    }
+    let keys = keys
+        .iter()
+        .map(|(key, exclusive)| quote! { (#key, #exclusive) });
+    Some(if is_async {
+        quote! { let _rstest_serial = rstest::serial::lock_async(&[#(#keys),*]).await; }
     } else {
-        None
+        quote! { let _rstest_serial = rstest::serial::lock(&[#(#keys),*]); }
+    })
+}
+
+/// Register the test metadata in `rstest::registry`: `attrs` are the test attributes left after
+/// removing the `rstest` ones.
+fn render_register(
+    name: &Ident,
+    testfn_name: &Ident,
+    report: &ReportInfo,
+    timeout: Option<&Expr>,
+    attrs: &[Attribute],
+) -> TokenStream {
+    let case = report
+        .case
+        .as_ref()
+        .map(|case| quote! { .with_case(#case) });
+    let parameters = (!report.parameters.is_empty()).then(|| {
+        let (args, values): (Vec<_>, Vec<_>) = report.parameters.iter().cloned().unzip();
+        quote! { .with_parameters(&[#((#args, #values)),*]) }
+    });
+    let tags = &report.tags;
+    let tags = (!tags.is_empty()).then(|| quote! { .with_tags(&[#(#tags),*]) });
+    let timeout = timeout.map(|timeout| quote! { .with_timeout(stringify!(#timeout)) });
+    let should_panic = attrs
+        .iter()
+        .any(|a| attr_is(a, "should_panic"))
+        .then(|| quote! { .with_should_panic() });
+    let ignore = attrs
+        .iter()
+        .any(|a| attr_is(a, "ignore"))
+        .then(|| quote! { .with_ignore() });
+    let attributes = (!attrs.is_empty()).then(|| {
+        quote! { .with_attributes(&[#(stringify!(#attrs)),*]) }
+    });
+    quote! {
+        #[cfg(test)]
+        rstest::registry::inventory::submit! {
+            rstest::registry::Test::new(
+                concat!(module_path!(), "::", stringify!(#name)),
+                stringify!(#testfn_name),
+                file!(),
+                line!(),
+                column!()
+            )
+            #case
+            #parameters
+            #tags
+            #timeout
+            #should_panic
+            #ignore
+            #attributes
+        }
+    }
+}
+
+/// Format the traced argument with `Debug`, `Display` or just its type name: the first
+/// implemented one.
+fn trace_value(arg: &Ident) -> TokenStream {
+    quote! {
+        {
+            use rstest::trace::*;
+            (&&&Printable(&#arg)).trace_format()
+        }
     }
 }
 
+/// The traced arguments are formatted before the test call and traced by `rstest::trace`
+/// runtime, immediately or only if the test fails. The test name is resolved by the test
+/// function in `rstest_test_name`.
+fn trace_arguments(testfn_name: &Ident, args: &[&Ident], on_failure: bool) -> Option<TokenStream> {
+    if args.is_empty() {
+        return None;
+    }
+    let values = args.iter().map(|arg| trace_value(arg));
+    let arguments = quote! {
+        rstest::trace::Arguments::new(
+            rstest_test_name,
+            stringify!(#testfn_name),
+            vec![#((stringify!(#args), #values)),*],
+        )
+    };
+    Some(if on_failure {
+        quote! { let _rstest_trace = rstest::trace::OnFailure::new(#arguments); }
+    } else {
+        quote! { #arguments.trace(); }
+    })
+}
+
 struct TestCaseRender<'a> {
     name: Ident,
     attrs: &'a [syn::Attribute],
     resolver: Box<dyn Resolver + 'a>,
+    report: ReportInfo,
 }
 
 impl<'a> TestCaseRender<'a> {
-    pub fn new<R: Resolver + 'a>(name: Ident, attrs: &'a [syn::Attribute], resolver: R) -> Self {
+    pub fn new<R: Resolver + 'a>(
+        name: Ident,
+        attrs: &'a [syn::Attribute],
+        resolver: R,
+        report: ReportInfo,
+    ) -> Self {
         TestCaseRender {
             name,
             attrs,
             resolver: Box::new(resolver),
+            report,
         }
     }
 
-    fn render(self, testfn: &ItemFn, attributes: &RsTestAttributes) -> TokenStream {
+    fn render(
+        self,
+        testfn: &ItemFn,
+        attributes: &RsTestAttributes,
+        arguments: &ArgumentsInfo,
+    ) -> TokenStream {
         let args = testfn.sig.inputs.iter().cloned().collect::<Vec<_>>();
         let mut attrs = testfn.attrs.clone();
         attrs.extend(self.attrs.iter().cloned());
@@ -350,7 +1115,9 @@ impl<'a> TestCaseRender<'a> {
             None,
             self.resolver,
             attributes,
+            arguments,
             &testfn.sig.generics,
+            &self.report,
         )
     }
 }
@@ -391,10 +1158,19 @@ fn format_case_name(case: &TestCase, index: usize, display_len: usize) -> String
     format!("case_{index:0display_len$}{description}")
 }
 
+/// Test name, attributes, resolver and report info of a case.
+type CaseData<'a> = (
+    Ident,
+    &'a [syn::Attribute],
+    HashMap<String, &'a syn::Expr>,
+    ReportInfo,
+);
+
 fn cases_data(
     data: &RsTestData,
     name_span: Span,
-) -> impl Iterator<Item = (Ident, &[syn::Attribute], HashMap<String, &syn::Expr>)> {
+    stable_names: bool,
+) -> impl Iterator<Item = CaseData<'_>> {
     let display_len = data.cases().count().display_len();
     data.cases().enumerate().map({
         move |(n, case)| {
@@ -403,10 +1179,25 @@ fn cases_data(
                 .map(|a| a.to_string())
                 .zip(case.args.iter())
                 .collect::<HashMap<_, _>>();
+            let name = if stable_names {
+                case.stable_name()
+            } else {
+                format_case_name(case, n + 1, display_len)
+            };
+            let report = ReportInfo {
+                case: case.description.as_ref().map(ToString::to_string),
+                parameters: data
+                    .case_args()
+                    .zip(case.args.iter())
+                    .map(|(arg, expr)| (arg.to_string(), expr.to_token_stream().to_string()))
+                    .collect(),
+                ..Default::default()
+            };
             (
-                Ident::new(&format_case_name(case, n + 1, display_len), name_span),
+                Ident::new(&name, name_span),
                 case.attrs.as_slice(),
                 resolver_case,
+                report,
             )
         }
     })
@@ -0,0 +1,149 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Ident;
+
+/// The async runtime used to run `async` tests when no test attribute is injected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Runtime {
+    AsyncStd,
+    Tokio { multi_thread: bool },
+    Smol,
+}
+
+/// The default runtime is selected by crate features (`tokio`, `tokio-multi-thread`, `smol`
+/// or `async-std`): if more than one is enabled `tokio` wins over `smol` and `smol` over
+/// `async-std`. If none is enabled use `async-std`.
+impl Default for Runtime {
+    fn default() -> Self {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "tokio-multi-thread")] {
+                Self::Tokio { multi_thread: true }
+            } else if #[cfg(feature = "tokio")] {
+                Self::Tokio { multi_thread: false }
+            } else if #[cfg(feature = "smol")] {
+                Self::Smol
+            } else {
+                Self::AsyncStd
+            }
+        }
+    }
+}
+
+impl Runtime {
+    pub(crate) const NAMES: &'static [&'static str] =
+        &["async_std", "tokio", "tokio_multi_thread", "smol"];
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "async_std" => Some(Self::AsyncStd),
+            "tokio" => Some(Self::Tokio {
+                multi_thread: false,
+            }),
+            "tokio_multi_thread" => Some(Self::Tokio { multi_thread: true }),
+            "smol" => Some(Self::Smol),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::AsyncStd => "async_std",
+            Self::Tokio {
+                multi_thread: false,
+            } => "tokio",
+            Self::Tokio { multi_thread: true } => "tokio_multi_thread",
+            Self::Smol => "smol",
+        }
+    }
+
+    /// The name of the test function when the test runs on more runtimes.
+    pub(crate) fn ident(&self, span: Span) -> Ident {
+        Ident::new(self.name(), span)
+    }
+
+    /// The attribute to mark the test function with.
+    pub(crate) fn test_attr(&self) -> TokenStream {
+        match self {
+            Self::AsyncStd => quote! { #[async_std::test] },
+            Self::Tokio {
+                multi_thread: false,
+            } => quote! { #[tokio::test] },
+            Self::Tokio { multi_thread: true } => {
+                quote! { #[tokio::test(flavor = "multi_thread")] }
+            }
+            Self::Smol => quote! { #[test] },
+        }
+    }
+
+    /// `smol` doesn't provide a test attribute: the test function should be a plain
+    /// function that blocks on the test body.
+    pub(crate) fn is_async_fn(&self) -> bool {
+        !matches!(self, Self::Smol)
+    }
+
+    pub(crate) fn wrap_body(&self, body: TokenStream) -> TokenStream {
+        match self {
+            Self::Smol => quote! { smol::block_on(async move { #body }) },
+            _ => body,
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    #[rstest]
+    #[case::async_std(Runtime::AsyncStd, "#[async_std::test]")]
+    #[case::tokio(Runtime::Tokio { multi_thread: false }, "#[tokio::test]")]
+    #[case::tokio_multi_thread(
+        Runtime::Tokio { multi_thread: true },
+        r#"#[tokio::test(flavor = "multi_thread")]"#
+    )]
+    #[case::smol(Runtime::Smol, "#[test]")]
+    fn use_the_runtime_test_attribute(#[case] runtime: Runtime, #[case] expected: &str) {
+        assert_eq!(attrs(expected), attrs(runtime.test_attr().to_string()));
+    }
+
+    #[test]
+    fn names_should_map_back_to_runtimes() {
+        for name in Runtime::NAMES {
+            assert_eq!(*name, Runtime::from_name(name).unwrap().name());
+        }
+        assert_eq!(None, Runtime::from_name("actix"));
+    }
+
+    #[test]
+    fn block_on_body_just_for_smol() {
+        let body = quote! { foo().await };
+
+        assert_eq!(
+            body.to_string(),
+            Runtime::AsyncStd.wrap_body(body.clone()).to_string()
+        );
+        assert_eq!(
+            quote! { smol::block_on(async move { foo().await }) }.to_string(),
+            Runtime::Smol.wrap_body(body).to_string()
+        );
+        assert!(!Runtime::Smol.is_async_fn());
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "tokio", feature = "tokio-multi-thread", feature = "smol")))]
+    fn use_async_std_by_default() {
+        assert_eq!(Runtime::AsyncStd, Runtime::default());
+    }
+
+    #[test]
+    #[cfg(feature = "tokio")]
+    fn use_tokio_if_enabled() {
+        assert!(matches!(Runtime::default(), Runtime::Tokio { .. }));
+    }
+
+    #[test]
+    #[cfg(all(feature = "smol", not(feature = "tokio")))]
+    fn use_smol_if_enabled() {
+        assert_eq!(Runtime::Smol, Runtime::default());
+    }
+}
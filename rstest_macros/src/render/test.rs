@@ -4,7 +4,7 @@ use syn::{
     parse::{Parse, ParseStream, Result},
     parse2, parse_str,
     visit::Visit,
-    ItemFn, ItemMod, LocalInit,
+    ItemFn, ItemMod, LocalInit, Stmt,
 };
 
 use super::*;
@@ -27,10 +27,13 @@ impl SetAsync for ItemFn {
 
 fn trace_argument_code_string(arg_name: &str) -> String {
     let arg_name = ident(arg_name);
-    let statment: Stmt = parse_quote! {
-        println!("{} = {:?}", stringify!(#arg_name) ,#arg_name);
+    let argument = quote! {
+        (stringify!(#arg_name), {
+            use rstest::trace::*;
+            (&&&Printable(&#arg_name)).trace_format()
+        })
     };
-    statment.display_code()
+    argument.display_code()
 }
 
 mod single_test_should {
@@ -52,6 +55,220 @@ mod single_test_should {
         assert_eq!(result.sig.output, input_fn.sig.output);
     }
 
+    #[rstest]
+    #[case::sync("", "rstest::outcome::catch_sync")]
+    #[case::async_fn("async", "rstest::outcome::catch_async")]
+    fn check_xfail_outcome_and_remove_return_type(#[case] prefix: &str, #[case] catch: &str) {
+        let mut input_fn: ItemFn =
+            format!("{prefix} fn function(fix: String) -> Result<i32, String> {{ Ok(42) }}").ast();
+        input_fn.attrs = attrs(r#"#[xfail(reason = "bug", strict = false)]"#);
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        assert_eq!(ReturnType::Default, result.sig.output);
+        assert!(result.attrs.iter().all(|a| !attr_is(a, "xfail")));
+        assert_in!(code, catch.ast::<syn::Path>().display_code());
+        assert_in!(code, r#"rstest :: xfail :: check"#);
+        assert_in!(code, r#""bug" , false)"#);
+    }
+
+    #[test]
+    fn check_skip_conditions_before_resolving_fixtures() {
+        let mut input_fn: ItemFn = "fn function(fix: String, other: u32) {}".ast();
+        input_fn.attrs = attrs(r#"#[skip_if(no_db(), reason = "no db")]"#);
+        let mut info = RsTestInfo::default();
+        info.arguments
+            .add_skip_if(ident("other"), "other > 42".ast());
+
+        let result: ItemFn = single(input_fn.clone(), info).ast();
+
+        let code = result.block.display_code();
+        let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("{s} not"));
+        assert!(result.attrs.iter().all(|a| !attr_is(a, "skip_if")));
+        assert!(position(r#"report ("no db")"#) < position("let other"));
+        assert!(position("let other") < position(r#"report ("other > 42")"#));
+        assert!(position(r#"report ("other > 42")"#) < position("let fix"));
+    }
+
+    #[rstest]
+    #[case::unit("", "return () ;")]
+    #[case::exit_code(
+        "-> std::process::ExitCode",
+        "return { use rstest :: outcome :: * ; (& & & PassedValue :: < std :: process :: ExitCode > (std :: marker :: PhantomData)) . passed () } ;"
+    )]
+    #[case::generic("-> T", "return rstest :: outcome :: Passed :: passed () ;")]
+    fn skip_returning_a_passing_value(#[case] output: &str, #[case] passed: &str) {
+        let mut input_fn: ItemFn =
+            format!("fn function<T: Passed>(fix: String) {output} {{ todo!() }}").ast();
+        input_fn.attrs = attrs("#[skip_if(true)]");
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        assert_in!(result.block.display_code(), passed);
+    }
+
+    #[test]
+    fn check_tags_of_untagged_tests_only_with_tags_feature() {
+        let input_fn: ItemFn = "fn function(fix: String) {}".ast();
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        assert_eq!(
+            cfg!(feature = "tags"),
+            result
+                .block
+                .display_code()
+                .contains("rstest :: tags :: check (& [])")
+        );
+    }
+
+    #[test]
+    fn use_watchdog_timeout_if_required() {
+        let mut input_fn: ItemFn = "fn function(fix: String) {}".ast();
+        input_fn.attrs = attrs("#[timeout(Duration::from_secs(1), watchdog)]");
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        assert_eq!(attrs("#[test]"), result.attrs);
+        assert_in!(code, "execute_with_watchdog_sync");
+        assert_not_in!(code, "execute_with_timeout_sync");
+    }
+
+    #[rstest]
+    #[case::sync("", "execute_with_default_timeout ()")]
+    #[case::async_fn("async", "execute_with_default_timeout_async")]
+    fn resolve_default_timeout_when_test_runs(#[case] prefix: &str, #[case] execute: &str) {
+        let input_fn: ItemFn = format!("{prefix} fn function(fix: String) {{}}").ast();
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        assert_in!(result.block.display_code(), execute);
+    }
+
+    #[test]
+    fn run_isolated_test_in_child_process_before_resolving_fixtures() {
+        let mut input_fn: ItemFn = "fn function(fix: String) {}".ast();
+        input_fn.attrs = attrs("#[isolate] #[timeout(Duration::from_secs(1))] #[should_panic]");
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("{s} not"));
+        assert_eq!(attrs("#[test] #[should_panic]"), result.attrs);
+        assert_in!(
+            code,
+            "rstest :: isolate :: run (& test_name , Some (Duration :: from_secs (1)) , true)"
+        );
+        assert!(position("rstest :: isolate :: run") < position("let fix"));
+        assert_not_in!(code, "execute_with_timeout_sync");
+        assert_not_in!(code, "DefaultTimeout");
+    }
+
+    #[test]
+    fn measure_test_with_slow_threshold() {
+        let mut input_fn: ItemFn = "fn function(fix: String) {}".ast();
+        input_fn.attrs = attrs("#[warn_slow(Duration::from_millis(200))]");
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("{s} not"));
+        assert_eq!(attrs("#[test]"), result.attrs);
+        assert_in!(code, "Some (Duration :: from_millis (200))");
+        assert!(position("rstest :: slow :: Stopwatch :: start") < position("let fix"));
+        assert!(position("let fix") < position("_rstest_stopwatch . failed ()"));
+    }
+
+    #[test]
+    fn measure_test_with_default_threshold_only_with_warn_slow_feature() {
+        let input_fn: ItemFn = "fn function(fix: String) {}".ast();
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        assert_eq!(
+            cfg!(feature = "warn-slow"),
+            code.contains("rstest :: slow :: default_threshold ()")
+        );
+    }
+
+    #[test]
+    fn isolate_by_env_only_with_isolate_feature() {
+        let input_fn: ItemFn = "fn function(fix: String) {}".ast();
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        assert_eq!(
+            cfg!(feature = "isolate"),
+            code.contains(
+                "if rstest :: isolate :: by_env () && ! rstest :: isolate :: is_isolated"
+            )
+        );
+    }
+
+    #[test]
+    fn isolated_test_should_use_default_timeout_in_parent_process() {
+        let mut input_fn: ItemFn = "fn function(fix: String) {}".ast();
+        input_fn.attrs = attrs("#[isolate]");
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        assert_in!(code, "rstest :: timeout :: default_timeout ()");
+        assert_not_in!(code, "DefaultTimeout");
+    }
+
+    #[test]
+    fn isolated_test_should_be_reported_by_parent_process_only_with_report_feature() {
+        let mut input_fn: ItemFn = "fn function(fix: String) {}".ast();
+        input_fn.attrs = attrs("#[isolate]");
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = result.block.display_code();
+        assert_eq!(
+            cfg!(feature = "report"),
+            code.contains(
+                "return rstest :: report :: run (rstest :: report :: Test :: new (module_path ! () , stringify ! (function)"
+            )
+        );
+    }
+
+    #[test]
+    fn render_a_test_for_each_runtime() {
+        let mut input_fn: ItemFn = "async fn function(fix: String) {}".ast();
+        input_fn.attrs = attrs("#[runtimes(tokio, smol)] #[should_panic]");
+
+        let result: ItemMod = single(input_fn.clone(), Default::default()).ast();
+
+        let tests = result
+            .content
+            .unwrap()
+            .1
+            .into_iter()
+            .filter_map(|it| match it {
+                syn::Item::Fn(f) => Some(f),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!("function", result.ident.to_string());
+        assert_eq!(
+            vec!["tokio", "smol"],
+            tests
+                .iter()
+                .map(|f| f.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(attrs("#[tokio::test] #[should_panic]"), tests[0].attrs);
+        assert!(tests[0].sig.asyncness.is_some());
+        assert_eq!(attrs("#[test] #[should_panic]"), tests[1].attrs);
+        assert!(tests[1].sig.asyncness.is_none());
+        assert_in!(tests[1].block.display_code(), "smol :: block_on");
+    }
+
     fn extract_inner_test_function(outer: &ItemFn) -> ItemFn {
         let first_stmt = outer.block.stmts.get(0).unwrap();
 
@@ -168,6 +385,42 @@ mod single_test_should {
         );
     }
 
+    #[test]
+    fn track_phases_of_fixtures_awaited_arguments_and_body() {
+        let mut input_fn: ItemFn = r#"async fn test(a: i32, b: i32) {} "#.ast();
+        input_fn.attrs = attrs("#[timeout(Duration::from_secs(1))]");
+        let mut info: RsTestInfo = Default::default();
+        info.arguments.set_future(ident("a"), FutureArg::Await);
+
+        let item_fn: ItemFn = single(input_fn.clone(), info).ast();
+
+        let code = item_fn.block.display_code();
+        let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("{s} not"));
+        let inner = extract_inner_test_function(&item_fn).block.display_code();
+        let inner_position = |s: &str| {
+            inner
+                .find(s)
+                .unwrap_or_else(|| panic!("{s} not in {inner}"))
+        };
+        assert!(position("Phase :: Fixture (stringify ! (a))") < position("let a = a :: default"));
+        assert!(position("let a = a :: default") < position("Phase :: Fixture (stringify ! (b))"));
+        assert!(position("let b = b :: default") < code.rfind("Phase :: Body").unwrap());
+        assert!(code.rfind("Phase :: Body").unwrap() < position("test (a , b) . await"));
+        assert!(inner_position("Phase :: Await") < inner_position("a . await"));
+        assert!(inner_position("a . await") < inner_position("Phase :: Body"));
+    }
+
+    #[test]
+    fn not_track_phases_without_timeout() {
+        let input_fn: ItemFn = r#"async fn test(a: i32) {} "#.ast();
+        let mut info: RsTestInfo = Default::default();
+        info.arguments.set_future(ident("a"), FutureArg::Await);
+
+        let item_fn: ItemFn = single(input_fn.clone(), info).ast();
+
+        assert_not_in!(item_fn.display_code(), "enter_phase");
+    }
+
     #[test]
     fn use_selective_await() {
         let input_fn: ItemFn = r#"fn test(a: i32, b:i32, c:i32) {} "#.ast();
@@ -197,6 +450,15 @@ mod single_test_should {
 
         let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
 
+        assert_in!(
+            item_fn.block.display_code(),
+            "let rstest_test_name = rstest :: isolate :: test_name (module_path ! () , stringify ! (test)) ;"
+        );
+        assert_in!(
+            item_fn.block.display_code(),
+            "rstest :: trace :: Arguments :: new (rstest_test_name , stringify ! (test) ,"
+        );
+        assert_in!(item_fn.block.display_code(), ". trace () ;");
         assert_in!(
             item_fn.block.display_code(),
             trace_argument_code_string("s")
@@ -243,6 +505,188 @@ mod single_test_should {
         );
     }
 
+    #[test]
+    fn trace_arguments_values_only_on_failure() {
+        let input_fn: ItemFn =
+            r#"#[trace(on_failure)] fn test(s: String, a:i32) -> Result<(), String> {}"#.ast();
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = item_fn.block.display_code();
+        assert_in!(code, trace_argument_code_string("s"));
+        assert_in!(code, trace_argument_code_string("a"));
+        assert_in!(
+            code,
+            "let _rstest_trace = rstest :: trace :: OnFailure :: new (rstest :: trace :: Arguments :: new"
+        );
+        assert_not_in!(code, ". trace () ;");
+        assert_in!(code, "_rstest_trace . check (test (s , a))");
+    }
+
+    #[rstest]
+    #[case::sync(false, "rstest :: report :: run (")]
+    #[case::async_fn(true, "rstest :: report :: run_async (")]
+    fn report_test_outcome(#[case] is_async: bool, #[case] run: &str) {
+        let code = render_report(
+            &ident("test"),
+            &ident("test"),
+            &Default::default(),
+            false,
+            &quote! { test(s) },
+            is_async,
+        )
+        .to_string();
+
+        assert_in!(code, run);
+        assert_in!(
+            code,
+            "rstest :: report :: Test :: new (module_path ! () , stringify ! (test) , stringify ! (test) , None , & [] , & [] , false)"
+        );
+        assert_in!(code, "Failure (output)) . failure ()");
+    }
+
+    #[test]
+    fn report_test_outcome_only_with_report_feature() {
+        let input_fn: ItemFn = r#"fn test(s: String) {}"#.ast();
+
+        let item_fn: ItemFn = single(input_fn, Default::default()).ast();
+
+        assert_eq!(
+            cfg!(feature = "report"),
+            item_fn
+                .block
+                .display_code()
+                .contains("rstest :: report :: run (")
+        );
+    }
+
+    #[rstest]
+    #[case::sync("", "rstest :: retry :: run (")]
+    #[case::async_fn("async", "rstest :: retry :: run_async (")]
+    fn retry_whole_test_body(#[case] prefix: &str, #[case] run: &str) {
+        let input_fn: ItemFn =
+            format!(r#"#[retry(3)] #[trace] {prefix} fn test(s: String) {{}}"#).ast();
+
+        let item_fn: ItemFn = single(input_fn, Default::default()).ast();
+
+        let code = item_fn.block.display_code();
+        let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("missed '{s}'"));
+        assert_in!(code, format!("{run}& rstest :: isolate :: test_name"));
+        assert_in!(code, "stringify ! (test)) , 3u32 ,");
+        assert!(position(run) < position("let rstest_test_name"));
+        assert!(position(run) < position("let s ="));
+        assert_eq!(
+            0,
+            item_fn.attrs.iter().filter(|a| attr_is(a, "retry")).count()
+        );
+    }
+
+    #[rstest]
+    #[case::sync("", "#[repeat(10)]", "rstest :: repeat :: run (")]
+    #[case::threads(
+        "",
+        "#[repeat(10, threads = 4)]",
+        "rstest :: repeat :: run_concurrent ("
+    )]
+    #[case::async_fn("async", "#[repeat(10)]", "rstest :: repeat :: run_async (")]
+    fn repeat_whole_test_body(#[case] prefix: &str, #[case] attr: &str, #[case] run: &str) {
+        let input_fn: ItemFn =
+            format!(r#"{attr} #[retry(2)] {prefix} fn test(s: String) {{}}"#).ast();
+
+        let item_fn: ItemFn = single(input_fn, Default::default()).ast();
+
+        let code = item_fn.block.display_code();
+        let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("missed '{s}'"));
+        assert_in!(code, "stringify ! (test)) , 10u32 ,");
+        assert!(position("rstest :: retry ::") < position(run));
+        assert!(position(run) < position("let s ="));
+    }
+
+    #[rstest]
+    #[case::sync("", "rstest :: serial :: lock (")]
+    #[case::async_fn("async", "rstest :: serial :: lock_async (")]
+    fn take_serial_keys_before_resolve_fixtures(#[case] prefix: &str, #[case] lock: &str) {
+        let input_fn: ItemFn = format!(
+            r#"#[rstest::serial(db)] #[rstest::parallel(db, port)] #[retry(2)] {prefix} fn test(s: String) {{}}"#
+        )
+        .ast();
+
+        let item_fn: ItemFn = single(input_fn, Default::default()).ast();
+
+        let code = item_fn.block.display_code();
+        let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("missed '{s}'"));
+        assert_in!(
+            code,
+            format!(r#"{lock}& [("db" , true) , ("port" , false)])"#)
+        );
+        assert!(position(lock) < position("rstest :: retry ::"));
+        assert!(position(lock) < position("let s ="));
+        assert!(item_fn
+            .attrs
+            .iter()
+            .all(|a| !attr_is_rstest(a, "serial") && !attr_is_rstest(a, "parallel")));
+    }
+
+    #[test]
+    fn leave_foreign_serial_attributes_untouched() {
+        let input_fn: ItemFn =
+            r#"#[serial] #[parallel(db)] #[serial_test::serial] fn test() {}"#.ast();
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        assert_eq!(input_fn.attrs, item_fn.attrs[1..]);
+        assert_not_in!(item_fn.block.display_code(), "rstest :: serial ::");
+    }
+
+    #[test]
+    fn register_test_metadata() {
+        let report = ReportInfo {
+            case: Some("one".to_owned()),
+            parameters: vec![("a".to_owned(), "1".to_owned())],
+            tags: vec!["db".to_owned()],
+        };
+
+        let code = render_register(
+            &ident("case_1_one"),
+            &ident("sum"),
+            &report,
+            Some(&expr("Duration::from_secs(1)")),
+            &attrs("#[should_panic] #[ignore]"),
+        )
+        .to_string();
+
+        assert_in!(code, "rstest :: registry :: inventory :: submit !");
+        assert_in!(
+            code,
+            "concat ! (module_path ! () , \"::\" , stringify ! (case_1_one)) , stringify ! (sum)"
+        );
+        assert_in!(code, ". with_case (\"one\")");
+        assert_in!(code, ". with_parameters (& [(\"a\" , \"1\")])");
+        assert_in!(code, ". with_tags (& [\"db\"])");
+        assert_in!(
+            code,
+            ". with_timeout (stringify ! (Duration :: from_secs (1)))"
+        );
+        assert_in!(code, ". with_should_panic () . with_ignore ()");
+    }
+
+    #[rstest]
+    #[case::fixtures("#[trace(fixtures)]", true)]
+    #[case::arguments_only("#[trace]", false)]
+    fn start_fixtures_trace_only_if_requested(#[case] attr: &str, #[case] expected: bool) {
+        let input_fn: ItemFn = format!(r#"{attr} fn test(s: String) {{}}"#).ast();
+
+        let item_fn: ItemFn = single(input_fn, Default::default()).ast();
+
+        assert_eq!(
+            expected,
+            item_fn
+                .block
+                .display_code()
+                .contains("rstest :: trace :: TraceFixtures :: start ()")
+        );
+    }
+
     #[rstest]
     #[case::sync("", parse_quote! { #[test] })]
     #[case::async_fn("async", parse_quote! { #[async_std::test] })]
@@ -501,6 +945,8 @@ mod cases_should {
     use crate::parse::{
         arguments::{ArgumentsInfo, FutureArg},
         rstest::RsTestItem,
+        testcase::TestCase,
+        Attribute as RsTestAttribute,
     };
 
     use super::{assert_eq, *};
@@ -552,6 +998,18 @@ mod cases_should {
             self.info.attributes.add_notraces(idents);
             self
         }
+
+        fn stable_names(mut self) -> Self {
+            self.info
+                .attributes
+                .append(RsTestAttribute::attr("stable_names"));
+            self
+        }
+
+        fn bench(mut self) -> Self {
+            self.info.attributes.append(RsTestAttribute::attr("bench"));
+            self
+        }
     }
 
     fn one_simple_case() -> (ItemFn, RsTestInfo) {
@@ -653,6 +1111,23 @@ mod cases_should {
         assert_eq!(given_attrs.as_slice(), &test_attrs[..l]);
     }
 
+    #[test]
+    fn track_phases_of_fixtures_but_not_of_case_arguments() {
+        let (_, info) = TestCaseBuilder::from(r#"fn test(v: i32){}"#)
+            .push_case(TestCase::from("42").with_attrs(attrs("#[timeout(Duration::from_secs(1))]")))
+            .take();
+        let item_fn: ItemFn = "fn test(v: i32, fix: String){}".ast();
+
+        let tokens = parametrize(item_fn, info);
+
+        let code = TestsGroup::from(tokens).get_all_tests()[0]
+            .block
+            .display_code();
+        assert_in!(code, "Phase :: Fixture (stringify ! (fix))");
+        assert_not_in!(code, "Phase :: Fixture (stringify ! (v))");
+        assert_in!(code, "Phase :: Body");
+    }
+
     #[test]
     fn mark_user_function_as_test() {
         let (item_fn, info) = TestCaseBuilder::from(
@@ -805,6 +1280,145 @@ mod cases_should {
             .ends_with(&format!("_{}", description)));
     }
 
+    #[test]
+    #[cfg(feature = "report")]
+    fn report_case_description_and_arguments() {
+        let (item_fn, info) = TestCaseBuilder::from(r#"fn test(a: u32, b: &str) {}"#)
+            .push_case(TestCase::from_iter(["42", r#""the answer""#]))
+            .push_case(TestCase::from_iter(["1", r#""one""#]).with_description("first"))
+            .take();
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        assert_in!(
+            tests[0].block.display_code(),
+            r#"stringify ! (test) , None , & [("a" , "42") , ("b" , "\"the answer\"")] , & [] , false)"#
+        );
+        assert_in!(
+            tests[1].block.display_code(),
+            r#"stringify ! (test) , Some ("first") , & [("a" , "1") , ("b" , "\"one\"")] , & [] , false)"#
+        );
+    }
+
+    #[test]
+    fn combine_case_tags_with_function_tags() {
+        let (mut item_fn, info) = TestCaseBuilder::from(r#"fn test(a: u32) {}"#)
+            .push_case(TestCase::from_iter(["1"]))
+            .push_case(TestCase::from_iter(["2"]).with_attrs(attrs("#[tag(db, slow)]")))
+            .take();
+        item_fn.attrs = attrs("#[tag(slow)]");
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        assert_in!(
+            tests[0].block.display_code(),
+            r#"rstest :: tags :: check (& ["slow"])"#
+        );
+        assert_in!(
+            tests[1].block.display_code(),
+            r#"rstest :: tags :: check (& ["slow" , "db"])"#
+        );
+        assert!(tests
+            .iter()
+            .flat_map(|t| t.attrs.iter())
+            .all(|a| !attr_is(a, "tag")));
+    }
+
+    #[test]
+    #[cfg(feature = "report")]
+    fn report_case_tags_with_function_tags() {
+        let (mut item_fn, info) = TestCaseBuilder::from(r#"fn test(a: u32) {}"#)
+            .push_case(TestCase::from_iter(["1"]))
+            .push_case(TestCase::from_iter(["2"]).with_attrs(attrs("#[tag(db, slow)]")))
+            .take();
+        item_fn.attrs = attrs("#[tag(slow)]");
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        assert_in!(
+            tests[0].block.display_code(),
+            r#"& [("a" , "1")] , & ["slow"] , false)"#
+        );
+        assert_in!(
+            tests[1].block.display_code(),
+            r#"& [("a" , "2")] , & ["slow" , "db"] , false)"#
+        );
+    }
+
+    #[rstest]
+    #[case::sync(
+        false,
+        "rstest :: bench :: run (& RSTEST_BENCH ,",
+        "RSTEST_BENCH . measure (move | | test (a))"
+    )]
+    #[case::async_fn(
+        true,
+        "rstest :: bench :: run_async (& RSTEST_BENCH ,",
+        "RSTEST_BENCH . measure_async (async move { test (a) . await }) . await"
+    )]
+    fn bench_should_measure_just_the_test_function_call(
+        #[case] is_async: bool,
+        #[case] run: &str,
+        #[case] measure: &str,
+    ) {
+        let (item_fn, info) = TestCaseBuilder::from(r#"fn test(a: u32) {}"#)
+            .set_async(is_async)
+            .bench()
+            .push_case(TestCase::from_iter(["42"]))
+            .push_case(TestCase::from_iter(["1"]))
+            .take();
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        assert_eq!(2, tests.len());
+        for test in tests {
+            let code = test.block.display_code();
+            let position = |s: &str| code.find(s).unwrap_or_else(|| panic!("missed '{s}'"));
+            assert_in!(
+                code,
+                "static RSTEST_BENCH : rstest :: bench :: Samples = rstest :: bench :: Samples :: new () ;"
+            );
+            assert!(position(run) < position("let a ="));
+            assert!(position("let a =") < position(measure));
+        }
+    }
+
+    #[test]
+    fn stable_names_should_not_depend_on_case_position() {
+        let (item_fn, info) = TestCaseBuilder::from(r#"fn test(a: u32, b: &str) {}"#)
+            .stable_names()
+            .push_case(TestCase::from_iter(["42", r#""the answer""#]))
+            .push_case(TestCase::from_iter(["1", r#""one""#]).with_description("first"))
+            .push_case(TestCase::from_iter([
+                "0",
+                &format!("{:?}", "a".repeat(100)),
+            ]))
+            .take();
+
+        let tokens = parametrize(item_fn, info);
+
+        let names = TestsGroup::from(tokens)
+            .get_all_tests()
+            .iter()
+            .map(|t| t.sig.ident.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!("case_42___theanswer__", &names[0]);
+        assert_eq!("case_first", &names[1]);
+        assert_eq!(
+            format!(
+                "case_0___{}_{:08x}",
+                "a".repeat(44),
+                stable_hash(&format!("0,{:?}", "a".repeat(100)))
+            ),
+            names[2]
+        );
+    }
+
     #[rstest]
     #[case::sync(false)]
     #[case::async_fn(true)]
@@ -1673,6 +2287,33 @@ mod matrix_cases_should {
         );
     }
 
+    #[test]
+    #[cfg(feature = "report")]
+    fn report_chosen_values() {
+        let info = RsTestInfo {
+            data: RsTestData {
+                items: vec![
+                    "first => [one => 1, 2]".ast::<ValueList>().into(),
+                    values_list("second", &["3"]).into(),
+                ],
+            },
+            ..Default::default()
+        };
+        let item_fn = r#"fn test(first: u32, second: u32) {}"#.ast();
+
+        let tokens = matrix(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).module.get_all_tests();
+        assert_in!(
+            tests[0].block.display_code(),
+            r#"None , & [("first" , "one") , ("second" , "3")]"#
+        );
+        assert_in!(
+            tests[1].block.display_code(),
+            r#"None , & [("first" , "2") , ("second" , "3")]"#
+        );
+    }
+
     #[test]
     fn pad_case_index() {
         let item_fn: ItemFn =
@@ -1708,6 +2349,139 @@ mod matrix_cases_should {
         assert_in!(functions[0], "third_1");
         assert_in!(functions[1], "third_2");
     }
+
+    #[test]
+    fn use_values_names_and_attributes() {
+        let item_fn: ItemFn = r#"fn test(first: u32, second: u32) { println!("user code") }"#.ast();
+        let info = RsTestInfo {
+            data: RsTestData {
+                items: vec![
+                    "first => [ok => 1, #[first_attr] 2]"
+                        .ast::<ValueList>()
+                        .into(),
+                    "second => [#[second_attr] big => 3, 4]"
+                        .ast::<ValueList>()
+                        .into(),
+                ],
+            },
+            ..Default::default()
+        };
+
+        let tokens = matrix(item_fn.clone(), info);
+
+        let tg = TestsGroup::from(tokens);
+        let modules = tg.get_modules();
+        assert_eq!(vec!["first_1_ok", "first_2_2"], modules.names());
+        assert_eq!(
+            vec!["second_1_big", "second_2_4"],
+            modules[0].get_tests().names()
+        );
+
+        let attrs_of = |m: usize, t: usize| {
+            modules[m].get_tests()[t]
+                .attrs
+                .iter()
+                .filter(|a| a.path().is_ident("first_attr") || a.path().is_ident("second_attr"))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(attrs("#[second_attr]"), attrs_of(0, 0));
+        assert!(attrs_of(0, 1).is_empty());
+        assert_eq!(attrs("#[first_attr] #[second_attr]"), attrs_of(1, 0));
+        assert_eq!(attrs("#[first_attr]"), attrs_of(1, 1));
+    }
+
+    fn flat(items: Vec<crate::parse::rstest::RsTestItem>) -> RsTestInfo {
+        let mut info: RsTestInfo = RsTestData { items }.into();
+        info.attributes
+            .append(crate::parse::Attribute::attr("flat"));
+        info
+    }
+
+    #[test]
+    fn flat_should_render_all_combinations_as_sibling_functions() {
+        let item_fn: ItemFn = r#"fn test(first: u32, second: u32) { println!("user code") }"#.ast();
+        let info = flat(vec![
+            values_list("first", &["1", "2"]).into(),
+            "second => [#[second_attr] three => 3, 4]"
+                .ast::<ValueList>()
+                .into(),
+        ]);
+
+        let tokens = matrix(item_fn.clone(), info);
+
+        let tg = TestsGroup::from(tokens);
+        assert!(tg.get_modules().is_empty());
+        let tests = tg.get_tests();
+        assert_eq!(
+            vec![
+                "first_1_1__second_1_three",
+                "first_1_1__second_2_4",
+                "first_2_2__second_1_three",
+                "first_2_2__second_2_4"
+            ],
+            tests.names()
+        );
+        assert!(tests[0].attrs.contains(&attrs("#[second_attr]")[0]));
+        assert!(!tests[1].attrs.contains(&attrs("#[second_attr]")[0]));
+    }
+
+    #[test]
+    fn flat_should_combine_cases_and_values() {
+        let item_fn: ItemFn = r#"fn test(a: u32, b: u32) { println!("user code") }"#.ast();
+        let info = flat(vec![
+            ident("a").into(),
+            TestCase::from("1").into(),
+            TestCase::from("2")
+                .with_description("two")
+                .with_attrs(attrs("#[case_attr]"))
+                .into(),
+            values_list("b", &["3", "4"]).into(),
+        ]);
+
+        let tokens = matrix(item_fn.clone(), info);
+
+        let tg = TestsGroup::from(tokens);
+        assert!(tg.get_modules().is_empty());
+        let tests = tg.get_tests();
+        assert_eq!(
+            vec![
+                "case_1__b_1_3",
+                "case_1__b_2_4",
+                "case_2_two__b_1_3",
+                "case_2_two__b_2_4"
+            ],
+            tests.names()
+        );
+        assert!(tests[3].attrs.contains(&attrs("#[case_attr]")[0]));
+        assert_in!(tests[2].block.display_code(), r#"let a = 2"#);
+        assert_in!(tests[2].block.display_code(), r#"let b = 3"#);
+        #[cfg(feature = "report")]
+        assert_in!(
+            tests[2].block.display_code(),
+            r#"Some ("two") , & [("a" , "2") , ("b" , "3")]"#
+        );
+    }
+
+    #[test]
+    fn flat_should_report_duplicated_names() {
+        let item_fn: ItemFn = r#"fn test(c: u32, a: u32) {}"#.ast();
+        let mut info = flat(vec![
+            ident("c").into(),
+            TestCase::from("1").with_description("q").into(),
+            TestCase::from("2").with_description("q__a_2_w").into(),
+            "a => [x => 1, w__a_1_x => 2]".ast::<ValueList>().into(),
+        ]);
+        info.attributes
+            .append(crate::parse::Attribute::attr("stable_names"));
+
+        let tokens = matrix(item_fn.clone(), info);
+
+        assert_in!(
+            tokens.to_string(),
+            "Duplicate test name 'case_q__a_2_w__a_1_x' in flat matrix"
+        );
+    }
 }
 
 mod complete_should {
@@ -1,5 +1,6 @@
 /// Contains some unsorted functions used across others modules
 ///
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
 use quote::format_ident;
 use std::collections::{HashMap, HashSet};
 use unicode_ident::is_xid_continue;
@@ -37,6 +38,16 @@ pub(crate) fn attr_is(attr: &Attribute, name: &str) -> bool {
     attr.path().is_ident(&format_ident!("{}", name))
 }
 
+/// Return if the attribute is the `rstest::<name>` one: used by the attributes whose bare
+/// name is taken by other widely used crates.
+pub(crate) fn attr_is_rstest(attr: &Attribute, name: &str) -> bool {
+    let segments = &attr.path().segments;
+    attr.path().leading_colon.is_none()
+        && segments.len() == 2
+        && segments[0].ident == "rstest"
+        && segments[1].ident == name
+}
+
 pub(crate) fn attr_in(attr: &Attribute, names: &[&str]) -> bool {
     names
         .iter()
@@ -286,6 +297,66 @@ pub(crate) fn sanitize_ident(name: &str) -> String {
         .collect()
 }
 
+/// Return true if the type refers to some generic type parameter or is an `impl Trait`:
+/// the traits implemented by such types are not known when the macro expands.
+pub(crate) fn type_uses_generics(t: &Type, generics: &Generics) -> bool {
+    let mut used: SearchSimpleTypeName = Default::default();
+    use syn::visit::Visit;
+    used.visit_type(t);
+    let used = used.take();
+    generics.type_params().any(|tp| used.contains(&tp.ident)) || {
+        #[derive(Default)]
+        struct SearchImplTrait(bool);
+
+        impl<'ast> syn::visit::Visit<'ast> for SearchImplTrait {
+            fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {
+                self.0 = true;
+            }
+        }
+        let mut search: SearchImplTrait = Default::default();
+        search.visit_type(t);
+        search.0
+    }
+}
+
+/// Print the tokens one by one separated by a single space: unlike `TokenStream::to_string()`,
+/// whose spacing is not guaranteed across compiler versions, the result depends only on the
+/// tokens.
+pub(crate) fn tokens_to_string(tokens: TokenStream) -> String {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let inner = tokens_to_string(group.stream());
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => return inner,
+                };
+                if inner.is_empty() {
+                    format!("{open}{close}")
+                } else {
+                    format!("{open} {inner} {close}")
+                }
+            }
+            TokenTree::Ident(ident) => ident.to_string(),
+            TokenTree::Punct(punct) => punct.as_char().to_string(),
+            TokenTree::Literal(literal) => literal.to_string(),
+        })
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A 32 bits FNV-1a hash: unlike `DefaultHasher` it's guaranteed to be the same across
+/// compiler versions and platforms, so it can be used to build names that never change.
+pub(crate) fn stable_hash(data: &str) -> u32 {
+    data.bytes().fold(0x811c9dc5, |hash, b| {
+        (hash ^ u32::from(b)).wrapping_mul(0x01000193)
+    })
+}
+
 #[cfg(test)]
 mod test {
     use syn::parse_quote;
@@ -423,4 +494,34 @@ mod test {
     fn sanitaze_ident_name(#[case] expression: impl AsRef<str>, #[case] expected: impl AsRef<str>) {
         assert_eq!(expected.as_ref(), sanitize_ident(expression.as_ref()));
     }
+
+    #[rstest]
+    #[case::concrete("u32", false)]
+    #[case::generic("T", true)]
+    #[case::nested_generic("Vec<Option<T>>", true)]
+    #[case::reference("&'a T", true)]
+    #[case::impl_trait("impl Iterator<Item = u32>", true)]
+    #[case::lifetime_only("&'a str", false)]
+    #[case::same_name_path("other::T", false)]
+    fn type_uses_generics_should(#[case] t: &str, #[case] expected: bool) {
+        let generics: Generics = parse_quote! { <'a, T: Clone> };
+
+        assert_eq!(expected, type_uses_generics(&t.ast(), &generics));
+    }
+
+    #[rstest]
+    #[case::literal(r#""a b""#, r#""a b""#)]
+    #[case::punct("a::b + -1", "a : : b + - 1")]
+    #[case::groups("f(x, [1, 2], {})", "f ( x , [ 1 , 2 ] , {} )")]
+    fn tokens_to_string_should_print_tokens_by_spaces(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(expected, tokens_to_string(code.parse().unwrap()));
+    }
+
+    #[rstest]
+    #[case("", 0x811c9dc5)]
+    #[case("a", 0xe40c292c)]
+    #[case("foobar", 0xbf9cf968)]
+    fn stable_hash_should_be_fnv1a(#[case] data: &str, #[case] expected: u32) {
+        assert_eq!(expected, stable_hash(data));
+    }
 }
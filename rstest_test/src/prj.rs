@@ -48,7 +48,7 @@ pub struct Project {
     channel: Channel,
     nocapture: bool,
     ws: Arc<std::sync::RwLock<()>>,
-    default_timeout: Option<u64>,
+    envs: Vec<(String, String)>,
 }
 
 impl Project {
@@ -61,7 +61,7 @@ impl Project {
             channel: Default::default(),
             nocapture: false,
             ws: Arc::new(std::sync::RwLock::new(())),
-            default_timeout: Default::default(),
+            envs: Default::default(),
         }
         .create()
     }
@@ -84,7 +84,7 @@ impl Project {
             channel: self.channel.clone(),
             nocapture: self.nocapture,
             ws: self.ws.clone(),
-            default_timeout: Default::default(),
+            envs: Default::default(),
         }
         .create()
     }
@@ -105,9 +105,7 @@ impl Project {
         }
         let mut cmd = Command::new("cargo");
 
-        if let Some(timeout) = self.default_timeout {
-            cmd.env("RSTEST_TIMEOUT", timeout.to_string());
-        }
+        cmd.envs(self.envs.iter().map(|(key, value)| (key, value)));
 
         cmd.current_dir(&self.path())
             .arg(&self.cargo_channel_arg())
@@ -257,8 +255,15 @@ impl Project {
         }
     }
 
-    // in seconds
-    pub fn set_default_timeout(&mut self, timeout: u64) {
-        self.default_timeout = Some(timeout);
+    // in seconds or with a unit (i.e. `500ms`)
+    pub fn set_default_timeout(&mut self, timeout: impl ToString) {
+        self.set_env("RSTEST_TIMEOUT", timeout);
+    }
+
+    /// Set an environment variable for the tests run
+    pub fn set_env(&mut self, key: impl ToString, value: impl ToString) {
+        let key = key.to_string();
+        self.envs.retain(|(k, _)| k != &key);
+        self.envs.push((key, value.to_string()));
     }
 }